@@ -2,6 +2,190 @@ use anchor_lang::prelude::*;
 
 declare_id!("HYubBywfVs4LzqZnP5dqrnxYqCMHTCd2vqKLpvj8KofF");
 
+/// ISO 3166-1 alpha-2 country codes, packed two characters per code
+pub const ISO_COUNTRY_CODES: &[&str] = &[
+    "ADAEAFAGAIALAMAOAQARASATAUAWAXAZ",
+    "BABBBDBEBFBGBHBIBJBLBMBNBOBQBRBSBTBVBWBYBZ",
+    "CACCCDCFCGCHCICKCLCMCNCOCRCUCVCWCXCYCZ",
+    "DEDJDKDMDODZ",
+    "ECEEEGEHERESET",
+    "FIFJFKFMFOFR",
+    "GAGBGDGEGFGGGHGIGLGMGNGPGQGRGSGTGUGWGY",
+    "HKHMHNHRHTHU",
+    "IDIEILIMINIOIQIRISIT",
+    "JEJMJOJP",
+    "KEKGKHKIKMKNKPKRKWKYKZ",
+    "LALBLCLILKLRLSLTLULVLY",
+    "MAMCMDMEMFMGMHMKMLMMMNMOMPMQMRMSMTMUMVMWMXMYMZ",
+    "NANCNENFNGNINLNONPNRNUNZ",
+    "OM",
+    "PAPEPFPGPHPKPLPMPNPRPSPTPWPY",
+    "QA",
+    "RERORSRURW",
+    "SASBSCSDSESGSHSISJSKSLSMSNSOSRSSSTSVSXSYSZ",
+    "TCTDTFTGTHTJTKTLTMTNTOTRTTTVTWTZ",
+    "UAUGUMUSUYUZ",
+    "VAVCVEVGVIVNVU",
+    "WFWS",
+    "YEYT",
+    "ZAZMZW",
+];
+
+/// Polygon vertex limits; the allowance grows with plot area up to the maximum
+pub const BASE_POLYGON_VERTICES: usize = 8;
+pub const MAX_POLYGON_VERTICES: usize = 16;
+pub const HECTARES_PER_EXTRA_VERTEX: f64 = 10.0;
+
+/// Discriminator for point + radius geometry, encoded as "point:<lat>,<lon>,<radius_meters>"
+pub const POINT_GEOMETRY_PREFIX: &str = "point:";
+/// A point's radius may exceed that of a circle of the plot's area by this factor,
+/// leaving room for an imprecise GPS fix
+pub const POINT_RADIUS_TOLERANCE: f64 = 1.5;
+/// Approximate length of a degree of latitude, for turning a point's radius into a box
+pub const METERS_PER_DEGREE_LAT: f64 = 111_320.0;
+pub const BYTES_PER_VERTEX: usize = 26;
+
+/// Byte limits for String fields, matching the space reserved in each account
+pub const MAX_ID_LEN: usize = 32;
+pub const MAX_NAME_LEN: usize = 64;
+/// Room for MAX_POLYGON_VERTICES vertices written as "[-12.345678,-123.456789],"
+/// plus the enclosing brackets
+pub const MAX_COORDINATES_LEN: usize = 2 + MAX_POLYGON_VERTICES * BYTES_PER_VERTEX;
+pub const MAX_HASH_LEN: usize = 64;
+pub const MAX_DESTINATION_LEN: usize = 64;
+pub const MAX_DDS_REFERENCE_LEN: usize = 64;
+pub const MAX_ACCREDITATION_ID_LEN: usize = 64;
+pub const MAX_PROVIDER_LEN: usize = 32;
+pub const MAX_CURRENCY_LEN: usize = 8;
+pub const MAX_PROCESSING_TYPE_LEN: usize = 48;
+
+/// How long an oracle has to answer a verification challenge
+pub const CHALLENGE_TTL_SECONDS: i64 = 300;
+
+/// Canopy cover (basis points) a plot may lose against its baseline and still
+/// verify clean
+pub const MAX_CANOPY_LOSS_BPS: i32 = 1_000;
+
+/// Layout version of the serialized DDSReport, bumped whenever fields are appended
+pub const DDS_VERSION: u8 = 15;
+
+/// Layout version of the serialized PlotAttestation
+pub const ATTESTATION_VERSION: u8 = 2;
+
+/// Bounding boxes may share up to this much extent (microdegrees, ~1m) on each
+/// axis before they count as overlapping, so neighbouring plots can touch
+pub const OVERLAP_TOLERANCE_E6: i64 = 10;
+
+pub const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Tolerance for client clocks running ahead of the cluster clock
+pub const MAX_CLOCK_SKEW_SECONDS: i64 = 300;
+
+/// Re-verification interval for commodities without a cadence of their own
+pub const DEFAULT_VERIFICATION_VALIDITY_SECONDS: i64 = 365 * SECONDS_PER_DAY;
+
+/// Score a plot drops to when its verification lapses
+pub const EXPIRED_COMPLIANCE_SCORE: u8 = 50;
+
+/// Score for a plot with a medium-confidence deforestation detection
+pub const MEDIUM_RISK_COMPLIANCE_SCORE: u8 = 50;
+
+/// Score a newly registered plot holds until its first verification
+pub const UNVERIFIED_COMPLIANCE_SCORE: u8 = 50;
+
+/// Score for a plot whose latest verification is disputed by a second oracle
+pub const CONTESTED_COMPLIANCE_SCORE: u8 = 60;
+
+/// Smoothing factor (percent) of the compliance score's moving average: a fully
+/// confident verification moves the score this far toward its own result
+pub const COMPLIANCE_EMA_ALPHA_PERCENT: i32 = 50;
+
+/// Account space for a FarmPlot (discriminator + data)
+pub const FARM_PLOT_SPACE: usize = 8 + 1000;
+
+/// Entries kept in a plot's AuditLog ring buffer
+pub const AUDIT_LOG_CAPACITY: usize = 16;
+pub const AUDIT_LOG_SPACE: usize = 8 + 32 + 4 + AUDIT_LOG_CAPACITY * 73 + 4 + 1;
+
+/// Verifications per VerificationIndex segment; a plot's Nth verification lives in segment N / capacity
+pub const VERIFICATION_INDEX_CAPACITY: u32 = 32;
+pub const VERIFICATION_INDEX_SPACE: usize = 8 + 32 + 4 + 4 + VERIFICATION_INDEX_CAPACITY as usize * 40 + 1;
+
+/// Most entries get_verifications_in_range returns, keeping it inside return-data limits
+pub const MAX_RANGE_RESULTS: usize = 24;
+
+/// Most status updates one get_route_history call returns, keeping it inside return-data
+/// limits; longer routes are read in pages of update accounts
+pub const MAX_ROUTE_HOPS: usize = 8;
+
+/// Deepest document merkle proof accepted, enough for 65,536 documents per batch
+pub const MAX_MERKLE_PROOF_LEN: usize = 16;
+
+/// CoordinateHistory: plot, revision, polygon, bounding box, reason, author, time, bump
+pub const COORDINATE_HISTORY_SPACE: usize = 8 + 32 + 2 + 4 + MAX_COORDINATES_LEN + 17 + 4 + MAX_NAME_LEN + 32 + 8 + 1;
+
+/// Version tag hashed into every checkpoint digest; bump it if the encoding changes
+pub const CHECKPOINT_VERSION: u8 = 1;
+
+/// Account space for a SatelliteVerification
+pub const SATELLITE_VERIFICATION_SPACE: usize = 8 + 400;
+
+/// Plots per register_plots_batch call, bounded by compute limits
+pub const MAX_PLOTS_PER_BATCH_REGISTRATION: usize = 10;
+
+/// remaining_accounts per plot in register_plots_batch: farm plot, audit log, commodity policy
+pub const PLOT_BATCH_ACCOUNTS_PER_PLOT: usize = 3;
+
+/// Plots per record_satellite_verification_batch call; each one costs four PDA derivations
+pub const MAX_PLOTS_PER_VERIFICATION_BATCH: usize = 5;
+
+/// remaining_accounts per plot in record_satellite_verification_batch: farm plot,
+/// verification, farmer profile, commodity policy, index segment, audit log, challenge
+pub const VERIFICATION_BATCH_ACCOUNTS_PER_PLOT: usize = 7;
+
+/// Maximum number of plots a single multi-plot batch can source from
+pub const MAX_SOURCE_PLOTS: usize = 5;
+
+/// Most input batches a processor can combine in one recorded input mix
+pub const MAX_MIX_INPUTS: usize = 5;
+
+/// Most FarmPlot accounts a single get_cooperative_stats call aggregates
+pub const MAX_PLOTS_PER_STATS_QUERY: usize = 20;
+
+/// Cattle batches reserve account space per ear tag, up to this many tags
+pub const MAX_EAR_TAGS: usize = 50;
+pub const MAX_EAR_TAG_LEN: usize = 24;
+
+/// Sustainability attribute keys a batch may carry
+pub const ALLOWED_ATTRIBUTE_KEYS: &[&str] = &[
+    "organic",
+    "fairtrade_cert",
+    "rainforest_alliance_cert",
+    "carbon_kg_co2e",
+    "water_usage_litres",
+    "smallholder",
+];
+pub const MAX_BATCH_ATTRIBUTES: usize = 8;
+pub const MAX_ATTRIBUTE_VALUE_LEN: usize = 32;
+
+/// External supply-chain systems a batch can be cross-referenced against;
+/// a batch holds at most one identifier per system
+pub const ALLOWED_EXTERNAL_SYSTEMS: &[&str] = &["gs1", "epcis", "traces"];
+pub const MAX_EXTERNAL_ID_LEN: usize = 64;
+
+/// Plots below this area qualify for EUDR simplified due diligence unless the
+/// admin configures a different threshold
+pub const DEFAULT_SMALLHOLDER_THRESHOLD_HECTARES: f64 = 4.0;
+
+/// Account space for a CommodityPolicy
+pub const COMMODITY_POLICY_SPACE: usize = 8 + 96;
+
+/// Minimum compliance score for batch registration when a commodity has no policy
+pub const DEFAULT_MIN_COMPLIANCE_SCORE: u8 = 70;
+/// Oracle tier a commodity with no policy requires, so its plots can't be verified
+/// by an arbitrary signer
+pub const DEFAULT_VERIFIER_TIER_REQUIRED: u8 = 1;
+
 #[program]
 pub mod farmtrace {
     use super::*;
@@ -13,38 +197,114 @@ pub mod farmtrace {
         plot_id: String,
         farmer_name: String,
         location: String,
+        country_code: [u8; 2],
         coordinates: String,
         area_hectares: f64,
         commodity_type: CommodityType,
+        commodity_label: String,
         registration_timestamp: i64,
+        gps_attestation: Option<GpsAttestation>,
+        harvest_season: Option<HarvestSeason>,
     ) -> Result<()> {
+        require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+        
+        // Coordinates signed by an approved capture device are marked as attested;
+        // self-declared coordinates are still accepted but left unattested
+        let gps_attested = match gps_attestation {
+            Some(attestation) => {
+                let device = ctx
+                    .accounts
+                    .approved_device
+                    .as_ref()
+                    .ok_or(ErrorCode::DeviceNotApproved)?;
+                require!(
+                    device.is_active && device.device == attestation.device_pubkey,
+                    ErrorCode::DeviceNotApproved
+                );
+                let ix_sysvar = ctx
+                    .accounts
+                    .instructions_sysvar
+                    .as_ref()
+                    .ok_or(ErrorCode::InvalidDeviceSignature)?;
+                verify_ed25519_ix(
+                    ix_sysvar,
+                    1,
+                    &attestation.device_pubkey,
+                    coordinates.as_bytes(),
+                    &attestation.device_signature,
+                    ErrorCode::InvalidDeviceSignature,
+                )?;
+                true
+            }
+            None => false,
+        };
+        
+        // High-risk commodities can require a second, registry-approved signer
+        let policy = CommodityPolicy::load_or_default(&ctx.accounts.commodity_policy, commodity_type)?;
+        let cosigned_by = resolve_cosigner(
+            ctx.accounts.cosigner.as_ref(),
+            ctx.accounts.approved_cosigner.as_ref(),
+        )?;
+        require!(!policy.requires_cosigner || cosigned_by.is_some(), ErrorCode::CosignerRequired);
+        
         let farm_plot = &mut ctx.accounts.farm_plot;
         
-        // Validate inputs
-        require!(plot_id.len() <= 32, ErrorCode::PlotIdTooLong);
-        require!(coordinates.len() <= 128, ErrorCode::InvalidCoordinates);
-        require!(area_hectares > 0.0, ErrorCode::InvalidArea);
-        
-        // Initialize farm plot data
-        farm_plot.plot_id = plot_id.clone();
-        farm_plot.farmer = ctx.accounts.farmer.key();
-        farm_plot.farmer_name = farmer_name;
-        farm_plot.location = location;
-        farm_plot.coordinates = coordinates;
-        farm_plot.area_hectares = area_hectares;
-        farm_plot.commodity_type = commodity_type;
-        farm_plot.registration_timestamp = registration_timestamp;
-        farm_plot.deforestation_risk = DeforestationRisk::Low;
-        farm_plot.compliance_score = 100;
-        farm_plot.last_verified = Clock::get()?.unix_timestamp;
-        farm_plot.is_active = true;
-        farm_plot.bump = ctx.bumps.farm_plot;
+        // The account is created with init_if_needed so a resubmitted plot_id gets a
+        // clear error instead of a raw account-in-use failure
+        require!(farm_plot.farmer == Pubkey::default(), ErrorCode::PlotAlreadyRegistered);
         
-        emit!(FarmPlotRegistered {
+        let input = PlotRegistrationInput {
             plot_id,
+            farmer_name,
+            location,
+            country_code,
+            coordinates,
+            area_hectares,
+            commodity_type,
+            commodity_label,
+            registration_timestamp,
+            harvest_season,
+        };
+        let smallholder_threshold = ctx.accounts.config.smallholder_threshold();
+        validate_plot_input(&input, &ctx.accounts.config.limits(), smallholder_threshold)?;
+        populate_farm_plot(
+            farm_plot,
+            input,
+            ctx.accounts.farmer.key(),
+            ctx.bumps.farm_plot,
+            Clock::get()?.unix_timestamp,
+            gps_attested,
+            smallholder_threshold,
+            policy.validity_window(),
+        );
+        farm_plot.cosigned_by = cosigned_by;
+        append_audit_entry(
+            &mut ctx.accounts.audit_log,
+            farm_plot.key(),
+            ctx.bumps.audit_log,
+            AuditAction::Registered,
+            farm_plot.farmer,
+            farm_plot.metadata_hash,
+        )?;
+        
+        // Aggregate into the farmer's profile, created on their first plot
+        let profile = &mut ctx.accounts.farmer_profile;
+        require!(!profile.suspended, ErrorCode::FarmerSuspended);
+        profile.farmer = farm_plot.farmer;
+        profile.bump = ctx.bumps.farmer_profile;
+        profile.total_plots = profile.total_plots.saturating_add(1);
+        profile.active_plots = profile.active_plots.saturating_add(1);
+        profile.compliance_score_sum += farm_plot.compliance_score as u64;
+        profile.refresh_average();
+        
+        emit!(FarmPlotRegistered {
+            plot_id: farm_plot.plot_id.clone(),
             farmer: farm_plot.farmer,
             coordinates: farm_plot.coordinates.clone(),
-            timestamp: registration_timestamp,
+            centroid_lat_microdeg: farm_plot.centroid_lat_microdeg,
+            centroid_lon_microdeg: farm_plot.centroid_lon_microdeg,
+            country_code: farm_plot.country_code,
+            timestamp: farm_plot.registration_timestamp,
         });
         
         msg!("Farm plot registered successfully!");
@@ -56,20 +316,39 @@ pub mod farmtrace {
     pub fn register_harvest_batch(
         ctx: Context<RegisterHarvestBatch>,
         batch_id: String,
-        weight_kg: u64,
+        weight: u64,
+        weight_unit: WeightUnit,
         harvest_timestamp: i64,
+        head_count: Option<u32>,
+        ear_tag_ids: Vec<String>,
+        expires_at: Option<i64>,
     ) -> Result<()> {
         let batch = &mut ctx.accounts.harvest_batch;
-        let farm_plot = &ctx.accounts.farm_plot;
-        
-        // Verify farm plot is compliant (EUDR requirement)
-        require!(
-            farm_plot.is_active && farm_plot.compliance_score >= 70,
-            ErrorCode::NonCompliantFarm
-        );
+        let farm_plot = &mut ctx.accounts.farm_plot;
+        require_keys_eq!(farm_plot.farmer, ctx.accounts.farmer.key(), ErrorCode::NotPlotOwner);
+        require!(!ctx.accounts.farmer_profile.suspended, ErrorCode::FarmerSuspended);
+        require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+        // Batches from a secondary crop on a multi-commodity plot carry that crop's commodity
+        let crop = ctx.accounts.crop.as_mut();
+        let commodity_type = crop.as_ref().map_or(farm_plot.commodity_type, |crop| crop.commodity_type);
+        let policy = CommodityPolicy::load_or_default(&ctx.accounts.commodity_policy, commodity_type)?;
         
-        require!(batch_id.len() <= 32, ErrorCode::BatchIdTooLong);
-        require!(weight_kg > 0, ErrorCode::InvalidWeight);
+        require_str_len(&batch_id, ctx.accounts.config.limits().max_batch_id_len as usize, ErrorCode::BatchIdTooLong)?;
+        require!(ctx.accounts.prior_dds_record.data_is_empty(), ErrorCode::BatchIdRetired);
+        let weight_kg = weight_unit.to_kg(weight)?;
+        validate_cattle_fields(commodity_type, head_count, &ear_tag_ids)?;
+        if let Some(expires_at) = expires_at {
+            require!(expires_at > harvest_timestamp, ErrorCode::InvalidBatchExpiry);
+        }
+        // An admin co-signature allows backfilling history out of harvest order
+        record_plot_harvest(
+            farm_plot,
+            crop.map(|crop| &mut **crop),
+            &policy,
+            weight_kg,
+            harvest_timestamp,
+            ctx.accounts.backfill_admin.is_some(),
+        )?;
         
         // Initialize harvest batch
         batch.batch_id = batch_id.clone();
@@ -77,11 +356,42 @@ pub mod farmtrace {
         batch.farmer = ctx.accounts.farmer.key();
         batch.weight_kg = weight_kg;
         batch.harvest_timestamp = harvest_timestamp;
-        batch.commodity_type = farm_plot.commodity_type;
+        batch.commodity_type = commodity_type;
         batch.status = BatchStatus::Harvested;
-        batch.compliance_status = ComplianceStatus::Compliant;
+        batch.compliance_status = policy.batch_compliance_status(farm_plot);
         batch.destination = String::new();
         batch.bump = ctx.bumps.harvest_batch;
+        batch.dds_reference = String::new();
+        batch.remaining_weight_kg = weight_kg;
+        batch.source_plots = Vec::new();
+        batch.source_weights_kg = Vec::new();
+        batch.pre_recall_status = None;
+        batch.recall_reason = String::new();
+        batch.sustainability_attrs = Vec::new();
+        batch.head_count = head_count;
+        batch.ear_tag_ids = ear_tag_ids;
+        batch.reported_weight = weight;
+        batch.reported_unit = weight_unit;
+        batch.update_seq = 0;
+        batch.external_ids = Vec::new();
+        batch.derivative_count = 0;
+        batch.traceable_weight_kg = weight_kg;
+        batch.expires_at = expires_at;
+        batch.lot_number = String::new();
+        batch.packaging_units = 0;
+        batch.export_locked = false;
+        batch.owner = batch.farmer;
+        batch.documents_merkle_root = [0; 32];
+        batch.recall_requested_by = None;
+        batch.processing_count = 0;
+        append_audit_entry(
+            &mut ctx.accounts.audit_log,
+            farm_plot.key(),
+            ctx.bumps.audit_log,
+            AuditAction::HarvestRegistered,
+            batch.farmer,
+            anchor_lang::solana_program::hash::hashv(&[batch.key().as_ref(), &weight_kg.to_le_bytes()]).to_bytes(),
+        )?;
         
         emit!(HarvestBatchRegistered {
             batch_id,
@@ -95,24 +405,90 @@ pub mod farmtrace {
     }
 
     /// Update batch status as it moves through supply chain
-    /// Tracks: Harvested → Processing → InTransit → Delivered
+    /// Tracks: Harvested → Processing → InTransit → (PartiallyDelivered) → Delivered
     pub fn update_batch_status(
         ctx: Context<UpdateBatchStatus>,
         new_status: BatchStatus,
         destination: String,
+        delivered_weight_kg: Option<u64>,
     ) -> Result<()> {
         let batch = &mut ctx.accounts.harvest_batch;
+        let previous_status = batch.status;
+        require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
         
-        require!(destination.len() <= 64, ErrorCode::DestinationTooLong);
+        require_str_len(
+            &destination,
+            ctx.accounts.config.limits().max_destination_len as usize,
+            ErrorCode::DestinationTooLong
+        )?;
+        // Recalled goods only move through recall_batch / resolve_recall
+        require!(!batch.is_recalled(), ErrorCode::InvalidStatusTransition);
+        require!(batch.status != BatchStatus::Consumed, ErrorCode::BatchConsumed);
+        // Terminal and recall states are only entered by their own instructions
+        // (deposit_to_lot, recall_batch / resolve_recall, mark_batch_expired)
+        require!(
+            !matches!(
+                new_status,
+                BatchStatus::Consumed | BatchStatus::Recalled | BatchStatus::RecallRequested | BatchStatus::Expired
+            ),
+            ErrorCode::InvalidStatusTransition
+        );
+        // Perishable goods can't be delivered past their shelf life
+        require!(batch.status != BatchStatus::Expired, ErrorCode::BatchExpired);
+        if let Some(expires_at) = batch.expires_at {
+            require!(
+                new_status != BatchStatus::Delivered || Clock::get()?.unix_timestamp <= expires_at,
+                ErrorCode::BatchExpired
+            );
+        }
         
-        batch.status = new_status;
+        // Deliveries may arrive in several shipments; only the last one completes the batch.
+        // Omitting the weight delivers whatever remains, under the same backing check
+        let delivered_weight_kg = match (new_status, delivered_weight_kg) {
+            (BatchStatus::Delivered, None) => Some(batch.remaining_weight_kg),
+            (_, weight) => weight,
+        };
+        batch.status = match (new_status, delivered_weight_kg) {
+            (BatchStatus::Delivered, Some(weight)) => {
+                require!(weight > 0, ErrorCode::InvalidWeight);
+                require!(weight <= batch.remaining_weight_kg, ErrorCode::OverDelivery);
+                let delivered_so_far = batch.weight_kg - batch.remaining_weight_kg;
+                require!(
+                    delivered_so_far.saturating_add(weight) <= batch.traceable_weight_kg,
+                    ErrorCode::UnbackedWeight
+                );
+                batch.remaining_weight_kg -= weight;
+                if batch.remaining_weight_kg == 0 {
+                    BatchStatus::Delivered
+                } else {
+                    BatchStatus::PartiallyDelivered
+                }
+            }
+            (_, Some(_)) => return err!(ErrorCode::InvalidStatusTransition),
+            (status, None) => status,
+        };
         batch.destination = destination.clone();
         
+        // Each update gets its own record, keyed on the batch's update sequence
+        let timestamp = Clock::get()?.unix_timestamp;
+        let status_update = &mut ctx.accounts.status_update;
+        status_update.harvest_batch = batch.key();
+        status_update.update_seq = batch.update_seq;
+        status_update.previous_status = previous_status;
+        status_update.new_status = batch.status;
+        status_update.destination = destination.clone();
+        status_update.delivered_weight_kg = delivered_weight_kg;
+        status_update.authority = ctx.accounts.authority.key();
+        status_update.timestamp = timestamp;
+        status_update.bump = ctx.bumps.status_update;
+        batch.update_seq = batch.update_seq.checked_add(1).ok_or(ErrorCode::ArithmeticOverflow)?;
+        
         emit!(BatchStatusUpdated {
             batch_id: batch.batch_id.clone(),
             new_status: batch.status,
             destination,
-            timestamp: Clock::get()?.unix_timestamp,
+            update_seq: status_update.update_seq,
+            timestamp,
         });
         
         msg!("Batch status updated successfully!");
@@ -126,67 +502,175 @@ pub mod farmtrace {
         verification_hash: String,
         no_deforestation: bool,
         verification_timestamp: i64,
+        confidence: u8,
+        verification_cost_lamports: u64,
+        provider: String,
+        imagery_date: i64,
+        oracle_message: SignedOracleMessage,
     ) -> Result<()> {
-        let farm_plot = &mut ctx.accounts.farm_plot;
-        let verification = &mut ctx.accounts.verification;
-        
-        require!(verification_hash.len() <= 64, ErrorCode::InvalidHash);
-        
-        // Store verification data
-        verification.farm_plot = farm_plot.key();
-        verification.verifier = ctx.accounts.verifier.key();
-        verification.verification_timestamp = verification_timestamp;
-        verification.verification_hash = verification_hash.clone();
-        verification.no_deforestation = no_deforestation;
-        verification.verification_type = VerificationType::Satellite;
-        verification.bump = ctx.bumps.verification;
-        
-        // Update farm compliance based on verification
-        if !no_deforestation {
-            farm_plot.deforestation_risk = DeforestationRisk::High;
-            farm_plot.compliance_score = 0;
-            msg!("WARNING: Deforestation detected!");
-        } else {
-            farm_plot.deforestation_risk = DeforestationRisk::Low;
-            if farm_plot.compliance_score < 100 {
-                farm_plot.compliance_score = 100;
+        // A zeroed verifier means init_if_needed just created the account
+        require!(
+            ctx.accounts.verification.verifier == Pubkey::default(),
+            ErrorCode::VerificationAlreadyRecorded
+        );
+        require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+        
+        require_str_len(&verification_hash, ctx.accounts.config.limits().max_hash_len as usize, ErrorCode::InvalidHash)?;
+        let verification_digest = parse_digest(&verification_hash)?;
+        require!(confidence <= 100, ErrorCode::InvalidConfidence);
+        validate_imagery_source(&provider, imagery_date)?;
+        
+        let policy = CommodityPolicy::load_or_default(&ctx.accounts.commodity_policy, ctx.accounts.farm_plot.commodity_type)?;
+        let submission = VerificationSubmission {
+            verifier: ctx.accounts.verifier.key(),
+            verification_hash: &verification_hash,
+            verification_digest,
+            no_deforestation,
+            verification_timestamp,
+            confidence,
+            provider: &provider,
+            imagery_date,
+            verification_cost_lamports,
+            oracle_message: &oracle_message,
+            oracle_message_ix: 1,
+        };
+        ctx.accounts.verification.bump = ctx.bumps.verification;
+        let verification_key = ctx.accounts.verification.key();
+        record_plot_verification(
+            VerificationAccounts {
+                farm_plot: &mut ctx.accounts.farm_plot,
+                farmer_profile: &mut ctx.accounts.farmer_profile,
+                verification: &mut ctx.accounts.verification,
+                verification_key,
+                audit_log: &mut ctx.accounts.audit_log,
+                audit_log_bump: ctx.bumps.audit_log,
+                verification_index: &mut ctx.accounts.verification_index,
+                verification_index_bump: ctx.bumps.verification_index,
+                registered_oracle: ctx.accounts.registered_oracle.as_deref(),
+                oracle_nonce: &mut ctx.accounts.oracle_nonce,
+                oracle_nonce_bump: ctx.bumps.oracle_nonce,
+                instructions_sysvar: &ctx.accounts.instructions_sysvar,
+                challenge: ctx.accounts.challenge.as_deref_mut(),
+            },
+            &submission,
+            &policy,
+            &ctx.accounts.config,
+        )?;
+        
+        // A cooperative funding pool reimburses the verifier; without one the
+        // verifier bears the cost and it's only recorded
+        if let Some(funding_pool) = ctx.accounts.funding_pool.as_ref() {
+            if verification_cost_lamports > 0 {
+                anchor_lang::system_program::transfer(
+                    CpiContext::new(
+                        ctx.accounts.system_program.to_account_info(),
+                        anchor_lang::system_program::Transfer {
+                            from: funding_pool.to_account_info(),
+                            to: ctx.accounts.verifier.to_account_info(),
+                        },
+                    ),
+                    verification_cost_lamports,
+                )?;
             }
         }
         
-        farm_plot.last_verified = verification.verification_timestamp;
-        
         emit!(SatelliteVerificationRecorded {
-            farm_plot: farm_plot.key(),
+            farm_plot: ctx.accounts.farm_plot.key(),
             verification_hash,
             compliant: no_deforestation,
-            timestamp: verification.verification_timestamp,
+            timestamp: verification_timestamp,
+            provider,
+            imagery_date,
         });
         
         msg!("Satellite verification recorded!");
         Ok(())
     }
 
+    /// Record a processing step (drying, fermentation, slaughter...) for a batch
+    /// Tracks the yield between raw input and processed output weights
+    pub fn record_processing(
+        ctx: Context<RecordProcessing>,
+        output_weight_kg: u64,
+        processing_type: String,
+    ) -> Result<()> {
+        let batch = &mut ctx.accounts.harvest_batch;
+        let record = &mut ctx.accounts.processing_record;
+        
+        require!(
+            matches!(batch.status, BatchStatus::Harvested | BatchStatus::Processing),
+            ErrorCode::InvalidStatusTransition
+        );
+        require!(!batch.export_locked, ErrorCode::BatchLocked);
+        require_str_len(&processing_type, MAX_PROCESSING_TYPE_LEN, ErrorCode::ProcessingTypeTooLong)?;
+        // Each step works on what the previous one left, e.g. drying then fermenting
+        let input_weight_kg = batch.traceable_weight_kg;
+        require!(output_weight_kg > 0, ErrorCode::InvalidWeight);
+        require!(output_weight_kg <= input_weight_kg, ErrorCode::InvalidYield);
+        
+        let yield_basis_points = (output_weight_kg as u128 * 10_000 / input_weight_kg as u128) as u16;
+        
+        record.harvest_batch = batch.key();
+        record.processing_type = processing_type.clone();
+        record.input_weight_kg = input_weight_kg;
+        record.output_weight_kg = output_weight_kg;
+        record.yield_basis_points = yield_basis_points;
+        record.processed_at = Clock::get()?.unix_timestamp;
+        record.bump = ctx.bumps.processing_record;
+        record.step = batch.processing_count;
+        
+        batch.processing_count = batch.processing_count.checked_add(1).ok_or(ErrorCode::ArithmeticOverflow)?;
+        batch.status = BatchStatus::Processing;
+        // Processing losses shrink the weight the harvest can back downstream
+        batch.traceable_weight_kg = batch.traceable_weight_kg.min(output_weight_kg);
+        
+        emit!(ProcessingRecorded {
+            batch_id: batch.batch_id.clone(),
+            processing_type,
+            input_weight_kg,
+            output_weight_kg,
+            yield_basis_points,
+            timestamp: record.processed_at,
+        });
+        
+        msg!("Processing recorded successfully!");
+        Ok(())
+    }
+
     /// Generate DDS (Due Diligence Statement) data for EUDR
     /// This compiles all required data for regulatory submission
+    /// Certification accounts for the batch or its plot may be passed via remaining_accounts
     pub fn generate_dds_data(
         ctx: Context<GenerateDDSData>,
     ) -> Result<DDSReport> {
-        let batch = &ctx.accounts.harvest_batch;
-        let farm_plot = &ctx.accounts.farm_plot;
+        require_keys_eq!(
+            ctx.accounts.harvest_batch.farm_plot,
+            ctx.accounts.farm_plot.key(),
+            ErrorCode::BatchPlotMismatch
+        );
+        require!(
+            ctx.accounts.harvest_batch.status != BatchStatus::Consumed,
+            ErrorCode::BatchConsumed
+        );
+        require!(
+            !ctx.accounts.farm_plot.verification_lapsed(Clock::get()?.unix_timestamp),
+            ErrorCode::VerificationExpired
+        );
+        check_in_season_verification(&ctx.accounts.farm_plot, ctx.accounts.verification.as_deref())?;
         
-        let dds_report = DDSReport {
-            batch_id: batch.batch_id.clone(),
-            plot_id: farm_plot.plot_id.clone(),
-            farmer: farm_plot.farmer,
-            coordinates: farm_plot.coordinates.clone(),
-            commodity_type: farm_plot.commodity_type,
-            harvest_timestamp: batch.harvest_timestamp,
-            weight_kg: batch.weight_kg,
-            no_deforestation_verified: farm_plot.deforestation_risk != DeforestationRisk::High,
-            compliance_score: farm_plot.compliance_score,
-            last_verified: farm_plot.last_verified,
-            registration_timestamp: farm_plot.registration_timestamp,
-        };
+        let certifications = collect_active_certifications(
+            ctx.remaining_accounts,
+            ctx.accounts.harvest_batch.key(),
+            ctx.accounts.farm_plot.key(),
+        )?;
+        let dds_report = build_dds_report(
+            &ctx.accounts.harvest_batch,
+            &ctx.accounts.farm_plot,
+            ctx.accounts.processing_record.as_deref(),
+            ctx.accounts.verification.as_deref(),
+            ctx.accounts.payment.as_deref(),
+            certifications,
+        );
         
         emit!(DDSReportGenerated {
             batch_id: dds_report.batch_id.clone(),
@@ -197,243 +681,7171 @@ pub mod farmtrace {
         msg!("DDS report generated successfully!");
         Ok(dds_report)
     }
-}
 
-// ============================================================================
-// Account Structures
-// ============================================================================
+    /// Create a bulk storage lot (silo, warehouse) for a single commodity
+    /// Batches lose individual identity once deposited, so the lot carries the compliance
+    pub fn create_storage_lot(
+        ctx: Context<CreateStorageLot>,
+        lot_id: String,
+        commodity_type: CommodityType,
+    ) -> Result<()> {
+        let lot = &mut ctx.accounts.storage_lot;
+        
+        require_str_len(&lot_id, MAX_ID_LEN, ErrorCode::LotIdTooLong)?;
+        
+        lot.lot_id = lot_id.clone();
+        lot.operator = ctx.accounts.operator.key();
+        lot.commodity_type = commodity_type;
+        lot.total_weight_kg = 0;
+        lot.batch_count = 0;
+        lot.blended_status = ComplianceStatus::Compliant;
+        lot.bump = ctx.bumps.storage_lot;
+        lot.withdrawal_count = 0;
+        
+        emit!(StorageLotCreated {
+            lot_id,
+            operator: lot.operator,
+            commodity_type,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Storage lot created successfully!");
+        Ok(())
+    }
 
-#[account]
-pub struct FarmPlot {
-    pub plot_id: String,                // max 32
-    pub farmer: Pubkey,
-    pub farmer_name: String,            // max 64
-    pub location: String,               // max 64
-    pub coordinates: String,            // max 128
-    pub area_hectares: f64,
-    pub commodity_type: CommodityType,
-    pub registration_timestamp: i64,
-    pub deforestation_risk: DeforestationRisk,
-    pub compliance_score: u8,
-    pub last_verified: i64,
-    pub is_active: bool,
-    pub bump: u8,
-}
+    /// Deposit a harvest batch into a storage lot
+    /// The lot's blended compliance becomes the weakest among all deposited batches
+    pub fn deposit_to_lot(ctx: Context<DepositToLot>) -> Result<()> {
+        let lot = &mut ctx.accounts.storage_lot;
+        let batch = &mut ctx.accounts.harvest_batch;
+        let deposit = &mut ctx.accounts.lot_deposit;
+        
+        require!(
+            batch.commodity_type == lot.commodity_type,
+            ErrorCode::CommodityMismatch
+        );
+        require!(batch.status != BatchStatus::Consumed, ErrorCode::BatchConsumed);
+        require!(!batch.is_recalled(), ErrorCode::InvalidStatusTransition);
+        
+        // Lots carry only the weight the deposited harvest can back
+        lot.total_weight_kg = lot
+            .total_weight_kg
+            .checked_add(batch.traceable_weight_kg)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        lot.batch_count = lot
+            .batch_count
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        lot.blended_status = lot.blended_status.weakest(batch.compliance_status);
+        
+        deposit.storage_lot = lot.key();
+        deposit.harvest_batch = batch.key();
+        deposit.weight_kg = batch.traceable_weight_kg;
+        deposit.compliance_status = batch.compliance_status;
+        deposit.deposited_at = Clock::get()?.unix_timestamp;
+        deposit.bump = ctx.bumps.lot_deposit;
+        
+        // The deposited weight now lives in the lot; the emptied batch is consumed
+        batch.traceable_weight_kg = 0;
+        if batch.consume_if_empty() {
+            emit!(HarvestBatchConsumed {
+                batch_id: batch.batch_id.clone(),
+                timestamp: deposit.deposited_at,
+            });
+        }
+        
+        emit!(LotDeposited {
+            lot_id: lot.lot_id.clone(),
+            batch_id: batch.batch_id.clone(),
+            weight_kg: deposit.weight_kg,
+            total_weight_kg: lot.total_weight_kg,
+            blended_status: lot.blended_status,
+            timestamp: deposit.deposited_at,
+        });
+        
+        msg!("Batch deposited to storage lot!");
+        Ok(())
+    }
 
-#[account]
-pub struct HarvestBatch {
-    pub batch_id: String,
-    pub farm_plot: Pubkey,
-    pub farmer: Pubkey,
-    pub weight_kg: u64,
-    pub harvest_timestamp: i64,
-    pub commodity_type: CommodityType,
-    pub status: BatchStatus,
-    pub compliance_status: ComplianceStatus,
-    pub destination: String,
-    pub bump: u8,
-}
+    /// Withdraw product from a storage lot
+    /// Withdrawn product inherits the lot's blended compliance status
+    pub fn withdraw_from_lot(
+        ctx: Context<WithdrawFromLot>,
+        weight_kg: u64,
+        destination: String,
+    ) -> Result<()> {
+        let lot = &mut ctx.accounts.storage_lot;
+        
+        require!(weight_kg > 0, ErrorCode::InvalidWeight);
+        require_str_len(
+            &destination,
+            ctx.accounts.config.limits().max_destination_len as usize,
+            ErrorCode::DestinationTooLong
+        )?;
+        require!(weight_kg <= lot.total_weight_kg, ErrorCode::InsufficientLotBalance);
+        
+        lot.total_weight_kg -= weight_kg;
+        
+        // Each withdrawal gets its own record, keyed on the lot's withdrawal sequence
+        let withdrawal = &mut ctx.accounts.withdrawal;
+        withdrawal.storage_lot = lot.key();
+        withdrawal.index = lot.withdrawal_count;
+        withdrawal.weight_kg = weight_kg;
+        withdrawal.blended_status = lot.blended_status;
+        withdrawal.destination = destination.clone();
+        withdrawal.operator = ctx.accounts.operator.key();
+        withdrawal.withdrawn_at = Clock::get()?.unix_timestamp;
+        withdrawal.bump = ctx.bumps.withdrawal;
+        lot.withdrawal_count = lot.withdrawal_count.checked_add(1).ok_or(ErrorCode::ArithmeticOverflow)?;
+        
+        emit!(LotWithdrawn {
+            lot_id: lot.lot_id.clone(),
+            weight_kg,
+            remaining_weight_kg: lot.total_weight_kg,
+            blended_status: lot.blended_status,
+            destination,
+            timestamp: withdrawal.withdrawn_at,
+        });
+        
+        msg!("Withdrawal from storage lot recorded!");
+        Ok(())
+    }
 
-#[account]
-pub struct SatelliteVerification {
-    pub farm_plot: Pubkey,
-    pub verifier: Pubkey,
-    pub verification_timestamp: i64,
-    pub verification_hash: String,
-    pub no_deforestation: bool,
-    pub verification_type: VerificationType,
-    pub bump: u8,
-}
+    /// Issue a fresh nonce the oracle must sign into its next verification of the plot
+    /// Only the plot's farmer or the config admin may issue, so a stolen oracle key
+    /// can't mint its own challenges
+    pub fn issue_verification_challenge(ctx: Context<IssueVerificationChallenge>, verifier: Pubkey) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+        require!(
+            authority == ctx.accounts.farm_plot.farmer || authority == ctx.accounts.config.admin,
+            ErrorCode::Unauthorized
+        );
+        let challenge = &mut ctx.accounts.challenge;
+        let clock = Clock::get()?;
+        
+        let nonce = anchor_lang::solana_program::hash::hashv(&[
+            ctx.accounts.farm_plot.key().as_ref(),
+            verifier.as_ref(),
+            &clock.slot.to_le_bytes(),
+            &clock.unix_timestamp.to_le_bytes(),
+        ])
+        .to_bytes();
+        
+        challenge.farm_plot = ctx.accounts.farm_plot.key();
+        challenge.verifier = verifier;
+        challenge.nonce = nonce;
+        challenge.issued_at = clock.unix_timestamp;
+        challenge.expires_at = clock.unix_timestamp + CHALLENGE_TTL_SECONDS;
+        challenge.consumed = false;
+        challenge.bump = ctx.bumps.challenge;
+        
+        emit!(VerificationChallengeIssued {
+            farm_plot: challenge.farm_plot,
+            verifier: challenge.verifier,
+            nonce,
+            expires_at: challenge.expires_at,
+        });
+        
+        msg!("Verification challenge issued!");
+        Ok(())
+    }
 
-// ============================================================================
-// Context Structures (with PDA seeds)
-// ============================================================================
+    /// Register a farm plot after checking it doesn't overlap its neighbours
+    /// Neighbouring FarmPlot accounts are passed via remaining_accounts and compared
+    /// by bounding box, a conservative first pass before full polygon intersection
+    pub fn register_farm_plot_checked(
+        ctx: Context<RegisterFarmPlot>,
+        plot_id: String,
+        farmer_name: String,
+        location: String,
+        country_code: [u8; 2],
+        coordinates: String,
+        area_hectares: f64,
+        commodity_type: CommodityType,
+        commodity_label: String,
+        registration_timestamp: i64,
+        gps_attestation: Option<GpsAttestation>,
+        harvest_season: Option<HarvestSeason>,
+    ) -> Result<()> {
+        // The box comes from the geometry itself, so it can't be understated to dodge the check
+        let bounding_box = plot_bounding_box(&coordinates).ok_or(ErrorCode::InvalidBoundingBox)?;
+        check_neighbor_overlap(&bounding_box, ctx.remaining_accounts, &ctx.accounts.farm_plot.key())?;
+        
+        ctx.accounts.farm_plot.bounding_box = Some(bounding_box);
+        register_farm_plot(
+            ctx,
+            plot_id,
+            farmer_name,
+            location,
+            country_code,
+            coordinates,
+            area_hectares,
+            commodity_type,
+            commodity_label,
+            registration_timestamp,
+            gps_attestation,
+            harvest_season,
+        )
+    }
 
-#[derive(Accounts)]
-#[instruction(plot_id: String)]
-pub struct RegisterFarmPlot<'info> {
-    #[account(
-        init,
-        payer = farmer,
-        space = 8 + 400, // discriminator + data
-        seeds = [b"farm_plot", plot_id.as_bytes(), farmer.key().as_ref()],
-        bump
-    )]
-    pub farm_plot: Account<'info, FarmPlot>,
-    
-    #[account(mut)]
-    pub farmer: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    /// Record a plot's land-cover baseline (canopy cover) at registration time
+    /// Later verifications report canopy cover that's compared against the baseline
+    /// Only the plot's farmer or a registered, active oracle may set it, once
+    pub fn record_land_cover_baseline(
+        ctx: Context<RecordLandCoverBaseline>,
+        canopy_cover_bps: u16,
+        land_cover_hash: String,
+        observed_timestamp: i64,
+    ) -> Result<()> {
+        let farm_plot = &mut ctx.accounts.farm_plot;
+        let baseline = &mut ctx.accounts.baseline;
+        
+        let authority = ctx.accounts.authority.key();
+        match ctx.accounts.registered_oracle.as_ref() {
+            Some(registered) => require!(registered.is_active, ErrorCode::OracleNotRegistered),
+            None => require_keys_eq!(authority, farm_plot.farmer, ErrorCode::Unauthorized),
+        }
+        require!(canopy_cover_bps <= 10_000, ErrorCode::InvalidCanopyCover);
+        require_str_len(&land_cover_hash, MAX_HASH_LEN, ErrorCode::InvalidHash)?;
+        
+        baseline.farm_plot = farm_plot.key();
+        baseline.recorded_by = authority;
+        baseline.canopy_cover_bps = canopy_cover_bps;
+        baseline.land_cover_hash = land_cover_hash.clone();
+        baseline.observed_timestamp = observed_timestamp;
+        baseline.bump = ctx.bumps.baseline;
+        
+        farm_plot.land_cover_baseline = Some(baseline.key());
+        farm_plot.baseline_canopy_cover_bps = Some(canopy_cover_bps);
+        append_audit_entry(
+            &mut ctx.accounts.audit_log,
+            farm_plot.key(),
+            ctx.bumps.audit_log,
+            AuditAction::BaselineRecorded,
+            baseline.recorded_by,
+            anchor_lang::solana_program::hash::hashv(&[baseline.land_cover_hash.as_bytes(), &canopy_cover_bps.to_le_bytes()]).to_bytes(),
+        )?;
+        
+        emit!(LandCoverBaselineRecorded {
+            farm_plot: farm_plot.key(),
+            canopy_cover_bps,
+            land_cover_hash,
+            timestamp: observed_timestamp,
+        });
+        
+        msg!("Land-cover baseline recorded!");
+        Ok(())
+    }
 
-#[derive(Accounts)]
-#[instruction(batch_id: String)]
-pub struct RegisterHarvestBatch<'info> {
-    #[account(
-        init,
-        payer = farmer,
-        space = 8 + 250,
-        seeds = [b"harvest_batch", batch_id.as_bytes(), farmer.key().as_ref()],
-        bump
-    )]
-    pub harvest_batch: Account<'info, HarvestBatch>,
-    
-    #[account(
-        seeds = [b"farm_plot", farm_plot.plot_id.as_bytes(), farmer.key().as_ref()],
-        bump = farm_plot.bump
-    )]
+    /// Initialize the program config, making the signer the admin
+    pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        
+        config.admin = ctx.accounts.admin.key();
+        config.bump = ctx.bumps.config;
+        config.string_limits = StringLimits {
+            max_plot_id_len: MAX_ID_LEN as u16,
+            max_batch_id_len: MAX_ID_LEN as u16,
+            max_name_len: MAX_NAME_LEN as u16,
+            max_coordinates_len: MAX_COORDINATES_LEN as u16,
+            max_destination_len: MAX_DESTINATION_LEN as u16,
+            max_hash_len: MAX_HASH_LEN as u16,
+        };
+        config.smallholder_threshold_hectares = DEFAULT_SMALLHOLDER_THRESHOLD_HECTARES;
+        config.oracle_stake_lamports = 0;
+        config.config_timelock_seconds = 0;
+        config.paused = false;
+        config.require_verification_challenge = false;
+        
+        msg!("Program config initialized!");
+        Ok(())
+    }
+
+    /// Set the compliance policy for a commodity (admin only)
+    /// Higher-risk commodities like palm oil and cattle can demand stricter scores
+    pub fn set_commodity_policy(
+        ctx: Context<SetCommodityPolicy>,
+        commodity_type: CommodityType,
+        min_compliance_score: u8,
+        verification_validity_seconds: i64,
+        min_verification_interval_seconds: i64,
+        max_yield_kg_per_hectare: u64,
+        requires_cosigner: bool,
+        min_age_before_harvest_seconds: i64,
+        smallholder_min_age_before_harvest_seconds: i64,
+        allow_provisional_medium_risk: bool,
+        verifier_tier_required: u8,
+    ) -> Result<()> {
+        let policy = &mut ctx.accounts.commodity_policy;
+        
+        let params = PolicyParams {
+            min_compliance_score,
+            verification_validity_seconds,
+            min_verification_interval_seconds,
+            max_yield_kg_per_hectare,
+            requires_cosigner,
+            min_age_before_harvest_seconds,
+            smallholder_min_age_before_harvest_seconds,
+            allow_provisional_medium_risk,
+            verifier_tier_required,
+        };
+        params.validate()?;
+        // Under a timelock a policy can be tightened directly but only loosened by proposal
+        if ctx.accounts.config.config_timelock_seconds > 0 {
+            let loosened = if policy.bump == 0 {
+                CommodityPolicy::default_for(commodity_type).is_loosened_by(&params)
+            } else {
+                policy.is_loosened_by(&params)
+            };
+            require!(!loosened, ErrorCode::TimelockRequired);
+        }
+        
+        policy.commodity_type = commodity_type;
+        policy.bump = ctx.bumps.commodity_policy;
+        policy.set_params(params);
+        
+        emit!(CommodityPolicyUpdated {
+            commodity_type,
+            min_compliance_score,
+            verification_validity_seconds,
+            min_verification_interval_seconds,
+            max_yield_kg_per_hectare,
+            requires_cosigner,
+            min_age_before_harvest_seconds,
+            smallholder_min_age_before_harvest_seconds,
+            allow_provisional_medium_risk,
+            verifier_tier_required,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Commodity policy updated!");
+        Ok(())
+    }
+
+    /// Assign the DDS reference number issued by the EU information system
+    /// once the batch's DDS has been recorded; it cannot be changed afterwards
+    pub fn assign_dds_reference(
+        ctx: Context<AssignDdsReference>,
+        reference_number: String,
+    ) -> Result<()> {
+        let batch = &mut ctx.accounts.harvest_batch;
+        
+        require!(!reference_number.is_empty(), ErrorCode::InvalidDDSReference);
+        require_str_len(&reference_number, MAX_DDS_REFERENCE_LEN, ErrorCode::InvalidDDSReference)?;
+        require!(batch.dds_reference.is_empty(), ErrorCode::ReferenceAlreadyAssigned);
+        
+        batch.dds_reference = reference_number.clone();
+        
+        emit!(DDSReferenceAssigned {
+            batch_id: batch.batch_id.clone(),
+            reference_number,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("DDS reference number assigned!");
+        Ok(())
+    }
+
+    /// Approve a GPS capture device whose signatures attest plot coordinates (admin only)
+    pub fn approve_gps_device(ctx: Context<ApproveGpsDevice>, device: Pubkey) -> Result<()> {
+        let approved = &mut ctx.accounts.approved_device;
+        
+        approved.device = device;
+        approved.approved_by = ctx.accounts.admin.key();
+        approved.is_active = true;
+        approved.bump = ctx.bumps.approved_device;
+        
+        emit!(GpsDeviceApprovalChanged {
+            device,
+            approved: true,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("GPS device approved!");
+        Ok(())
+    }
+
+    /// Revoke a previously approved GPS device (admin only)
+    pub fn revoke_gps_device(ctx: Context<RevokeGpsDevice>) -> Result<()> {
+        let approved = &mut ctx.accounts.approved_device;
+        
+        approved.is_active = false;
+        
+        emit!(GpsDeviceApprovalChanged {
+            device: approved.device,
+            approved: false,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("GPS device revoked!");
+        Ok(())
+    }
+
+    /// Add a cooperative officer to the cosigner registry (admin only)
+    pub fn approve_cosigner(ctx: Context<ApproveCosigner>, cosigner: Pubkey) -> Result<()> {
+        let approved = &mut ctx.accounts.approved_cosigner;
+        
+        approved.cosigner = cosigner;
+        approved.approved_by = ctx.accounts.admin.key();
+        approved.is_active = true;
+        approved.bump = ctx.bumps.approved_cosigner;
+        
+        emit!(CosignerApprovalChanged {
+            cosigner,
+            approved: true,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Cosigner approved!");
+        Ok(())
+    }
+
+    /// Revoke a previously approved cosigner (admin only)
+    pub fn revoke_cosigner(ctx: Context<RevokeCosigner>) -> Result<()> {
+        let approved = &mut ctx.accounts.approved_cosigner;
+        
+        approved.is_active = false;
+        
+        emit!(CosignerApprovalChanged {
+            cosigner: approved.cosigner,
+            approved: false,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Cosigner revoked!");
+        Ok(())
+    }
+
+    /// Add a satellite oracle to the registry (admin only)
+    pub fn approve_oracle(ctx: Context<ApproveOracle>, oracle: Pubkey, tier: u8) -> Result<()> {
+        let registered = &mut ctx.accounts.registered_oracle;
+        
+        registered.oracle = oracle;
+        registered.approved_by = ctx.accounts.admin.key();
+        registered.is_active = true;
+        registered.bump = ctx.bumps.registered_oracle;
+        registered.stake_remaining = 0;
+        registered.tier = tier;
+        
+        emit!(OracleApprovalChanged {
+            oracle,
+            approved: true,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Oracle approved!");
+        Ok(())
+    }
+
+    /// Revoke a previously registered oracle (admin only)
+    pub fn revoke_oracle(ctx: Context<RevokeOracle>) -> Result<()> {
+        let registered = &mut ctx.accounts.registered_oracle;
+        
+        registered.is_active = false;
+        
+        emit!(OracleApprovalChanged {
+            oracle: registered.oracle,
+            approved: false,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Oracle revoked!");
+        Ok(())
+    }
+
+    /// Persist a hash of the batch's DDS so it survives the batch account being closed
+    /// Certification accounts are passed via remaining_accounts, as for generate_dds_data
+    pub fn record_dds(ctx: Context<RecordDDS>) -> Result<()> {
+        require!(
+            ctx.accounts.harvest_batch.status != BatchStatus::Consumed,
+            ErrorCode::BatchConsumed
+        );
+        require!(
+            !ctx.accounts.farm_plot.verification_lapsed(Clock::get()?.unix_timestamp),
+            ErrorCode::VerificationExpired
+        );
+        check_in_season_verification(&ctx.accounts.farm_plot, ctx.accounts.verification.as_deref())?;
+        
+        let certifications = collect_active_certifications(
+            ctx.remaining_accounts,
+            ctx.accounts.harvest_batch.key(),
+            ctx.accounts.farm_plot.key(),
+        )?;
+        let report = build_dds_report(
+            &ctx.accounts.harvest_batch,
+            &ctx.accounts.farm_plot,
+            ctx.accounts.processing_record.as_deref(),
+            ctx.accounts.verification.as_deref(),
+            ctx.accounts.payment.as_deref(),
+            certifications,
+        );
+        let mut serialized = Vec::new();
+        report
+            .serialize(&mut serialized)
+            .map_err(|_| error!(ErrorCode::SerializationFailed))?;
+        
+        let record = &mut ctx.accounts.dds_record;
+        record.harvest_batch = ctx.accounts.harvest_batch.key();
+        record.farm_plot = ctx.accounts.farm_plot.key();
+        record.batch_id = report.batch_id.clone();
+        record.report_hash = anchor_lang::solana_program::hash::hash(&serialized).to_bytes();
+        record.compliance_score = report.compliance_score;
+        record.dds_version = report.dds_version;
+        record.recorded_at = Clock::get()?.unix_timestamp;
+        record.bump = ctx.bumps.dds_record;
+        
+        emit!(DDSRecorded {
+            batch_id: report.batch_id,
+            report_hash: record.report_hash,
+            timestamp: record.recorded_at,
+        });
+        
+        msg!("DDS record stored!");
+        Ok(())
+    }
+
+    /// Close a delivered or consumed batch and return its rent to the farmer
+    /// A delivered batch needs a DDS record first so traceability outlives the batch account
+    pub fn close_batch(ctx: Context<CloseBatch>) -> Result<()> {
+        let batch = &ctx.accounts.harvest_batch;
+        
+        // Consumed batches are traced through their lot deposit and need no DDS of their own
+        if batch.status != BatchStatus::Consumed {
+            require!(batch.status == BatchStatus::Delivered, ErrorCode::BatchNotDelivered);
+            require!(ctx.accounts.dds_record.is_some(), ErrorCode::DDSRequiredBeforeClose);
+        }
+        
+        emit!(BatchClosed {
+            batch_id: batch.batch_id.clone(),
+        });
+        
+        msg!("Batch closed!");
+        Ok(())
+    }
+
+    /// Register a harvest batch sourced from several of the farmer's plots
+    /// Writable source plots are passed via remaining_accounts with a parallel list of
+    /// weights; the batch takes the weakest compliance status among them
+    pub fn register_multiplot_batch(
+        ctx: Context<RegisterMultiplotBatch>,
+        batch_id: String,
+        commodity_type: CommodityType,
+        plot_weights_kg: Vec<u64>,
+        harvest_timestamp: i64,
+    ) -> Result<()> {
+        let farmer = ctx.accounts.farmer.key();
+        let policy = CommodityPolicy::load_or_default(&ctx.accounts.commodity_policy, commodity_type)?;
+        require!(!ctx.accounts.farmer_profile.suspended, ErrorCode::FarmerSuspended);
+        require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+        
+        require_str_len(&batch_id, ctx.accounts.config.limits().max_batch_id_len as usize, ErrorCode::BatchIdTooLong)?;
+        require!(ctx.accounts.prior_dds_record.data_is_empty(), ErrorCode::BatchIdRetired);
+        require!(
+            !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.len() <= MAX_SOURCE_PLOTS,
+            ErrorCode::InvalidSourcePlots
+        );
+        require!(
+            plot_weights_kg.len() == ctx.remaining_accounts.len(),
+            ErrorCode::InvalidSourcePlots
+        );
+        
+        let mut source_plots = Vec::with_capacity(plot_weights_kg.len());
+        let mut weight_kg: u64 = 0;
+        let mut compliance_status = ComplianceStatus::Compliant;
+        for (info, plot_weight) in ctx.remaining_accounts.iter().zip(plot_weights_kg.iter()) {
+            require_keys_eq!(*info.owner, crate::ID, ErrorCode::InvalidSourcePlots);
+            require!(info.is_writable, ErrorCode::InvalidSourcePlots);
+            require!(!source_plots.contains(info.key), ErrorCode::InvalidSourcePlots);
+            require!(*plot_weight > 0, ErrorCode::InvalidWeight);
+            
+            let mut data = info.try_borrow_mut_data()?;
+            let mut plot = FarmPlot::try_deserialize(&mut &data[..])?;
+            require_keys_eq!(plot.farmer, farmer, ErrorCode::Unauthorized);
+            require!(plot.commodity_type == commodity_type, ErrorCode::CommodityMismatch);
+            // Each source plot passes the same gates and bookkeeping as a single-plot harvest
+            record_plot_harvest(&mut plot, None, &policy, *plot_weight, harvest_timestamp, false)?;
+            plot.try_serialize(&mut &mut data[..])?;
+            
+            compliance_status = compliance_status.weakest(plot.compliance_status);
+            weight_kg = weight_kg
+                .checked_add(*plot_weight)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+            source_plots.push(*info.key);
+        }
+        
+        let batch = &mut ctx.accounts.harvest_batch;
+        batch.batch_id = batch_id.clone();
+        batch.farm_plot = source_plots[0];
+        batch.farmer = farmer;
+        batch.weight_kg = weight_kg;
+        batch.harvest_timestamp = harvest_timestamp;
+        batch.commodity_type = commodity_type;
+        batch.status = BatchStatus::Harvested;
+        batch.compliance_status = compliance_status;
+        batch.destination = String::new();
+        batch.bump = ctx.bumps.harvest_batch;
+        batch.dds_reference = String::new();
+        batch.remaining_weight_kg = weight_kg;
+        batch.source_plots = source_plots;
+        batch.source_weights_kg = plot_weights_kg;
+        batch.pre_recall_status = None;
+        batch.recall_reason = String::new();
+        batch.sustainability_attrs = Vec::new();
+        batch.head_count = None;
+        batch.ear_tag_ids = Vec::new();
+        batch.reported_weight = weight_kg;
+        batch.reported_unit = WeightUnit::Kilogram;
+        batch.update_seq = 0;
+        batch.external_ids = Vec::new();
+        batch.derivative_count = 0;
+        batch.traceable_weight_kg = weight_kg;
+        batch.expires_at = None;
+        batch.lot_number = String::new();
+        batch.packaging_units = 0;
+        batch.export_locked = false;
+        batch.owner = batch.farmer;
+        batch.documents_merkle_root = [0; 32];
+        batch.recall_requested_by = None;
+        batch.processing_count = 0;
+        
+        emit!(HarvestBatchRegistered {
+            batch_id,
+            farm_plot: batch.farm_plot,
+            weight_kg,
+            timestamp: harvest_timestamp,
+        });
+        
+        msg!("Multi-plot harvest batch registered successfully!");
+        Ok(())
+    }
+
+    /// Request a recall of a batch (contamination, buyer rejection, non-compliance)
+    /// Callable by the farmer or the title holder; freezes status updates until resolved
+    pub fn recall_batch(ctx: Context<RecallBatch>, reason: String) -> Result<()> {
+        let batch = &mut ctx.accounts.harvest_batch;
+        let authority = ctx.accounts.authority.key();
+        
+        require!(authority == batch.farmer || authority == batch.owner, ErrorCode::Unauthorized);
+        require!(!reason.is_empty(), ErrorCode::InvalidRecallReason);
+        require_str_len(&reason, MAX_NAME_LEN, ErrorCode::InvalidRecallReason)?;
+        require!(!batch.is_recalled(), ErrorCode::InvalidStatusTransition);
+        
+        batch.pre_recall_status = Some(batch.status);
+        batch.status = BatchStatus::RecallRequested;
+        batch.recall_reason = reason.clone();
+        batch.recall_requested_by = Some(authority);
+        
+        emit!(BatchRecalled {
+            batch_id: batch.batch_id.clone(),
+            reason,
+        });
+        
+        msg!("Batch recall requested!");
+        Ok(())
+    }
+
+    /// Resolve a pending recall, either finalizing it as Recalled or clearing it back
+    /// to the prior status; the requester or the admin decides, never the farmer
+    pub fn resolve_recall(ctx: Context<RecallBatch>, finalize: bool) -> Result<()> {
+        let batch = &mut ctx.accounts.harvest_batch;
+        let authority = ctx.accounts.authority.key();
+        
+        require!(batch.status == BatchStatus::RecallRequested, ErrorCode::NoPendingRecall);
+        require_keys_neq!(authority, batch.farmer, ErrorCode::Unauthorized);
+        require!(
+            batch.recall_requested_by == Some(authority) || authority == ctx.accounts.config.admin,
+            ErrorCode::Unauthorized
+        );
+        
+        batch.status = if finalize {
+            BatchStatus::Recalled
+        } else {
+            batch.pre_recall_status.ok_or(ErrorCode::NoPendingRecall)?
+        };
+        batch.pre_recall_status = None;
+        batch.recall_requested_by = None;
+        if !finalize {
+            batch.recall_reason = String::new();
+        }
+        
+        emit!(RecallResolved {
+            batch_id: batch.batch_id.clone(),
+            finalized: finalize,
+            status: batch.status,
+        });
+        
+        msg!("Batch recall resolved!");
+        Ok(())
+    }
+
+    /// Return a computed compliance snapshot for a plot in a single call
+    /// `is_batch_eligible` applies the same gate as register_harvest_batch
+    pub fn get_compliance_snapshot(ctx: Context<GetComplianceSnapshot>) -> Result<ComplianceSnapshot> {
+        let farm_plot = &ctx.accounts.farm_plot;
+        let policy = CommodityPolicy::load_or_default(&ctx.accounts.commodity_policy, farm_plot.commodity_type)?;
+        let now = Clock::get()?.unix_timestamp;
+        
+        Ok(ComplianceSnapshot {
+            plot_id: farm_plot.plot_id.clone(),
+            raw_score: farm_plot.compliance_score,
+            effective_score: effective_compliance_score(farm_plot, now),
+            risk: farm_plot.deforestation_risk,
+            last_verified: farm_plot.last_verified,
+            days_since_verification: (now.saturating_sub(farm_plot.last_verified).max(0) / SECONDS_PER_DAY) as u32,
+            is_batch_eligible: check_plot_eligible(farm_plot, &policy).is_ok(),
+        })
+    }
+
+    /// Set a batch's sustainability attributes (organic, fairtrade certificate...)
+    /// Keys must come from ALLOWED_ATTRIBUTE_KEYS so the data stays structured
+    pub fn set_batch_attributes(
+        ctx: Context<SetBatchAttributes>,
+        attributes: Vec<BatchAttribute>,
+    ) -> Result<()> {
+        require!(attributes.len() <= MAX_BATCH_ATTRIBUTES, ErrorCode::TooManyAttributes);
+        for attribute in attributes.iter() {
+            require!(
+                ALLOWED_ATTRIBUTE_KEYS.contains(&attribute.key.as_str()),
+                ErrorCode::UnknownAttributeKey
+            );
+            require_str_len(&attribute.value, MAX_ATTRIBUTE_VALUE_LEN, ErrorCode::AttributeValueTooLong)?;
+        }
+        
+        let batch = &mut ctx.accounts.harvest_batch;
+        batch.sustainability_attrs = attributes;
+        
+        emit!(BatchAttributesUpdated {
+            batch_id: batch.batch_id.clone(),
+            attribute_count: batch.sustainability_attrs.len() as u8,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Batch attributes updated!");
+        Ok(())
+    }
+
+    /// Check off-chain plot metadata against the hash anchored at registration
+    /// Fails if either the stored plot data or the claimed hash has been tampered with
+    pub fn verify_metadata_hash(
+        ctx: Context<VerifyMetadataHash>,
+        claimed_hash: [u8; 32],
+    ) -> Result<()> {
+        let farm_plot = &ctx.accounts.farm_plot;
+        
+        require!(
+            plot_metadata_hash(farm_plot) == farm_plot.metadata_hash
+                && claimed_hash == farm_plot.metadata_hash,
+            ErrorCode::MetadataHashMismatch
+        );
+        
+        msg!("Metadata hash verified!");
+        Ok(())
+    }
+
+    /// Flag a plot whose verification has lapsed so it's due for re-verification
+    /// Anyone can crank this; the plot drops to Medium risk until re-verified
+    pub fn mark_expired(ctx: Context<MarkExpired>) -> Result<()> {
+        let farm_plot = &mut ctx.accounts.farm_plot;
+        let now = Clock::get()?.unix_timestamp;
+        
+        require!(farm_plot.verification_lapsed(now), ErrorCode::VerificationNotExpired);
+        require!(!farm_plot.verification_expired, ErrorCode::AlreadyMarkedExpired);
+        
+        let old_score = farm_plot.compliance_score;
+        let old_risk = farm_plot.deforestation_risk;
+        // Expiry never improves a plot that's already flagged worse
+        let new_risk = if old_risk == DeforestationRisk::High {
+            DeforestationRisk::High
+        } else {
+            DeforestationRisk::Medium
+        };
+        set_compliance(farm_plot, i32::from(old_score.min(EXPIRED_COMPLIANCE_SCORE)), new_risk, "expired");
+        farm_plot.verification_expired = true;
+        ctx.accounts.farmer_profile.record_compliance_change(farm_plot, old_score, old_risk);
+        append_audit_entry(
+            &mut ctx.accounts.audit_log,
+            farm_plot.key(),
+            ctx.bumps.audit_log,
+            AuditAction::Expired,
+            Pubkey::default(),
+            anchor_lang::solana_program::hash::hashv(&[&farm_plot.verification_expires_at.to_le_bytes()]).to_bytes(),
+        )?;
+        
+        emit!(VerificationExpired {
+            farm_plot: farm_plot.key(),
+            expired_at: farm_plot.verification_expires_at,
+            timestamp: now,
+        });
+        
+        msg!("Plot verification marked expired!");
+        Ok(())
+    }
+
+    /// Register several plots in one transaction for cooperative onboarding
+    /// Each plot passes [farm_plot, audit_log, commodity_policy] via remaining_accounts in
+    /// the same order as `plots`; GPS attestation isn't supported on this path
+    pub fn register_plots_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RegisterPlotsBatch<'info>>,
+        plots: Vec<PlotRegistrationInput>,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+        require!(
+            !plots.is_empty() && plots.len() <= MAX_PLOTS_PER_BATCH_REGISTRATION,
+            ErrorCode::InvalidPlotBatch
+        );
+        require!(
+            plots.len() * PLOT_BATCH_ACCOUNTS_PER_PLOT == ctx.remaining_accounts.len(),
+            ErrorCode::InvalidPlotBatch
+        );
+        
+        let farmer = ctx.accounts.farmer.key();
+        let now = Clock::get()?.unix_timestamp;
+        let rent = Rent::get()?;
+        let rent_lamports = rent.minimum_balance(FARM_PLOT_SPACE) + rent.minimum_balance(AUDIT_LOG_SPACE);
+        let count = plots.len() as u32;
+        // Fail up front rather than partway through with a system program error
+        require_rent_balance(
+            &ctx.accounts.farmer.to_account_info(),
+            rent_lamports.saturating_mul(count as u64),
+        )?;
+        let limits = ctx.accounts.config.limits();
+        let smallholder_threshold = ctx.accounts.config.smallholder_threshold();
+        // One cosigner covers the whole batch; each plot's policy decides whether it's needed
+        let cosigned_by = resolve_cosigner(
+            ctx.accounts.cosigner.as_ref(),
+            ctx.accounts.approved_cosigner.as_ref(),
+        )?;
+        let payer = ctx.accounts.farmer.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let mut compliance_score_sum = 0u64;
+        
+        for (input, accounts) in plots.into_iter().zip(ctx.remaining_accounts.chunks(PLOT_BATCH_ACCOUNTS_PER_PLOT)) {
+            let [plot_info, audit_info, policy_info] = accounts else {
+                return err!(ErrorCode::InvalidPlotBatch);
+            };
+            validate_plot_input(&input, &limits, smallholder_threshold)?;
+            
+            let (expected_policy, _) =
+                Pubkey::find_program_address(&[b"policy", &[input.commodity_type as u8]], &crate::ID);
+            require_keys_eq!(*policy_info.key, expected_policy, ErrorCode::InvalidPlotBatch);
+            let policy = CommodityPolicy::load_or_default(policy_info, input.commodity_type)?;
+            require!(!policy.requires_cosigner || cosigned_by.is_some(), ErrorCode::CosignerRequired);
+            
+            let (expected, bump) = Pubkey::find_program_address(
+                &[b"farm_plot", input.plot_id.as_bytes(), farmer.as_ref()],
+                &crate::ID,
+            );
+            require_keys_eq!(*plot_info.key, expected, ErrorCode::InvalidPlotBatch);
+            require!(plot_info.data_is_empty(), ErrorCode::PlotAlreadyRegistered);
+            let (expected_audit, audit_bump) =
+                Pubkey::find_program_address(&[b"audit", expected.as_ref()], &crate::ID);
+            require_keys_eq!(*audit_info.key, expected_audit, ErrorCode::InvalidPlotBatch);
+            require!(audit_info.data_is_empty(), ErrorCode::InvalidPlotBatch);
+            
+            create_pda_account(
+                &payer,
+                plot_info,
+                &system_program,
+                FARM_PLOT_SPACE,
+                &[b"farm_plot", input.plot_id.as_bytes(), farmer.as_ref(), &[bump]],
+            )?;
+            create_pda_account(
+                &payer,
+                audit_info,
+                &system_program,
+                AUDIT_LOG_SPACE,
+                &[b"audit", expected.as_ref(), &[audit_bump]],
+            )?;
+            
+            // A freshly created account is zeroed, which decodes as an empty plot
+            let mut data = plot_info.try_borrow_mut_data()?;
+            let mut farm_plot = FarmPlot::try_deserialize_unchecked(&mut &data[..])?;
+            populate_farm_plot(
+                &mut farm_plot,
+                input,
+                farmer,
+                bump,
+                now,
+                false,
+                smallholder_threshold,
+                policy.validity_window(),
+            );
+            farm_plot.cosigned_by = cosigned_by;
+            compliance_score_sum += farm_plot.compliance_score as u64;
+            farm_plot.try_serialize(&mut &mut data[..])?;
+            
+            let mut audit_data = audit_info.try_borrow_mut_data()?;
+            let mut audit_log = AuditLog::try_deserialize_unchecked(&mut &audit_data[..])?;
+            append_audit_entry(
+                &mut audit_log,
+                expected,
+                audit_bump,
+                AuditAction::Registered,
+                farmer,
+                farm_plot.metadata_hash,
+            )?;
+            audit_log.try_serialize(&mut &mut audit_data[..])?;
+        }
+        
+        let profile = &mut ctx.accounts.farmer_profile;
+        require!(!profile.suspended, ErrorCode::FarmerSuspended);
+        profile.farmer = farmer;
+        profile.bump = ctx.bumps.farmer_profile;
+        profile.total_plots = profile.total_plots.saturating_add(count);
+        profile.active_plots = profile.active_plots.saturating_add(count);
+        profile.compliance_score_sum += compliance_score_sum;
+        profile.refresh_average();
+        
+        emit!(BatchPlotsRegistered {
+            farmer,
+            count,
+            timestamp: now,
+        });
+        
+        msg!("Plots registered successfully!");
+        Ok(())
+    }
+
+    /// Return a batch's provenance (plot, compliance, verifications, processing) in one call
+    /// SatelliteVerification, ProcessingRecord and DerivativeProduct accounts are passed via
+    /// remaining_accounts; anything not belonging to this batch or its plot is rejected
+    pub fn get_batch_provenance(ctx: Context<GetBatchProvenance>) -> Result<BatchProvenance> {
+        let batch = &ctx.accounts.harvest_batch;
+        let farm_plot = &ctx.accounts.farm_plot;
+        
+        let mut verifications = Vec::new();
+        let mut processing = None;
+        let mut derivatives = Vec::new();
+        for info in ctx.remaining_accounts.iter() {
+            require_keys_eq!(*info.owner, crate::ID, ErrorCode::InvalidProvenanceAccount);
+            let data = info.try_borrow_data()?;
+            if let Ok(verification) = SatelliteVerification::try_deserialize(&mut &data[..]) {
+                require_keys_eq!(verification.farm_plot, farm_plot.key(), ErrorCode::InvalidProvenanceAccount);
+                verifications.push(ProvenanceVerification {
+                    verifier: verification.verifier,
+                    verification_timestamp: verification.verification_timestamp,
+                    no_deforestation: verification.no_deforestation,
+                    confidence: verification.confidence,
+                });
+            } else if let Ok(record) = ProcessingRecord::try_deserialize(&mut &data[..]) {
+                require_keys_eq!(record.harvest_batch, batch.key(), ErrorCode::InvalidProvenanceAccount);
+                processing = Some(ProcessingStep {
+                    processing_type: record.processing_type,
+                    input_weight_kg: record.input_weight_kg,
+                    output_weight_kg: record.output_weight_kg,
+                    yield_basis_points: record.yield_basis_points,
+                    processed_at: record.processed_at,
+                });
+            } else if let Ok(derivative) = DerivativeProduct::try_deserialize(&mut &data[..]) {
+                require_keys_eq!(derivative.parent_batch, batch.key(), ErrorCode::InvalidProvenanceAccount);
+                derivatives.push(ProvenanceDerivative {
+                    index: derivative.index,
+                    product_name: derivative.product_name,
+                    output_weight_kg: derivative.output_weight_kg,
+                    created_at: derivative.created_at,
+                });
+            } else {
+                return err!(ErrorCode::InvalidProvenanceAccount);
+            }
+        }
+        verifications.sort_by_key(|verification| verification.verification_timestamp);
+        derivatives.sort_by_key(|derivative| derivative.index);
+        
+        Ok(BatchProvenance {
+            batch_id: batch.batch_id.clone(),
+            status: batch.status,
+            weight_kg: batch.weight_kg,
+            remaining_weight_kg: batch.remaining_weight_kg,
+            harvest_timestamp: batch.harvest_timestamp,
+            destination: batch.destination.clone(),
+            source_plots: batch.source_plots.clone(),
+            plot_id: farm_plot.plot_id.clone(),
+            farmer: farm_plot.farmer,
+            country_code: farm_plot.country_code,
+            commodity_type: farm_plot.commodity_type,
+            compliance_score: farm_plot.compliance_score,
+            deforestation_risk: farm_plot.deforestation_risk,
+            verifications,
+            processing,
+            derivatives,
+        })
+    }
+
+    /// Pre-submission sweep: reports whether a batch would pass EUDR checks today
+    /// and every concrete reason it wouldn't
+    pub fn check_batch_eligibility(ctx: Context<CheckBatchEligibility>) -> Result<EligibilityResult> {
+        let batch = &ctx.accounts.harvest_batch;
+        let farm_plot = &ctx.accounts.farm_plot;
+        let policy = CommodityPolicy::load_or_default(&ctx.accounts.commodity_policy, farm_plot.commodity_type)?;
+        
+        let mut failures = plot_eligibility_failures(farm_plot, &policy, Clock::get()?.unix_timestamp);
+        failures.extend(batch_eligibility_failures(batch));
+        
+        Ok(EligibilityResult {
+            eligible: failures.is_empty(),
+            failing_reasons: failures
+                .iter()
+                .map(|failure| failure.describe().to_string())
+                .collect(),
+        })
+    }
+
+    /// Update the String input limits (admin only); zero restores a limit's ceiling
+    pub fn set_string_limits(ctx: Context<UpdateConfig>, string_limits: StringLimits) -> Result<()> {
+        require!(string_limits.within_ceilings(), ErrorCode::StringLimitAboveCeiling);
+        
+        let config = &mut ctx.accounts.config;
+        require!(config.config_timelock_seconds == 0, ErrorCode::TimelockRequired);
+        config.string_limits = string_limits;
+        
+        emit!(StringLimitsUpdated {
+            string_limits,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("String limits updated!");
+        Ok(())
+    }
+
+    /// Map the batch to an identifier in an external supply-chain system
+    /// The system tag must come from ALLOWED_EXTERNAL_SYSTEMS
+    pub fn add_external_id(
+        ctx: Context<AddExternalId>,
+        system: String,
+        id: String,
+    ) -> Result<()> {
+        require!(
+            ALLOWED_EXTERNAL_SYSTEMS.contains(&system.as_str()),
+            ErrorCode::UnknownExternalSystem
+        );
+        require!(!id.is_empty(), ErrorCode::InvalidExternalId);
+        require_str_len(&id, MAX_EXTERNAL_ID_LEN, ErrorCode::InvalidExternalId)?;
+        
+        let batch = &mut ctx.accounts.harvest_batch;
+        require!(
+            !batch.external_ids.iter().any(|external| external.system == system),
+            ErrorCode::ExternalIdAlreadySet
+        );
+        batch.external_ids.push(ExternalId {
+            system: system.clone(),
+            id: id.clone(),
+        });
+        
+        emit!(ExternalIdAdded {
+            batch_id: batch.batch_id.clone(),
+            system,
+            id,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("External id added!");
+        Ok(())
+    }
+
+    /// Set the plot area below which simplified due diligence applies (admin only)
+    pub fn set_smallholder_threshold(ctx: Context<UpdateConfig>, threshold_hectares: f64) -> Result<()> {
+        require!(
+            threshold_hectares.is_finite() && threshold_hectares > 0.0,
+            ErrorCode::InvalidArea
+        );
+        require!(ctx.accounts.config.config_timelock_seconds == 0, ErrorCode::TimelockRequired);
+        
+        ctx.accounts.config.smallholder_threshold_hectares = threshold_hectares;
+        
+        msg!("Smallholder threshold updated!");
+        Ok(())
+    }
+
+    /// Set the stake an oracle must lock to register and contest verifications (admin only)
+    pub fn set_oracle_stake_requirement(ctx: Context<UpdateConfig>, stake_lamports: u64) -> Result<()> {
+        require!(ctx.accounts.config.config_timelock_seconds == 0, ErrorCode::TimelockRequired);
+        
+        ctx.accounts.config.oracle_stake_lamports = stake_lamports;
+        
+        msg!("Oracle stake requirement updated!");
+        Ok(())
+    }
+
+    /// Pause or resume plot/batch registration, status updates and verifications (admin only)
+    pub fn set_program_paused(ctx: Context<UpdateConfig>, paused: bool) -> Result<()> {
+        require!(ctx.accounts.config.config_timelock_seconds == 0, ErrorCode::TimelockRequired);
+        
+        ctx.accounts.config.paused = paused;
+        
+        emit!(ProgramPauseChanged {
+            paused,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Program pause updated!");
+        Ok(())
+    }
+
+    /// Require every satellite verification to answer an issued challenge (admin only)
+    pub fn set_challenge_required(ctx: Context<UpdateConfig>, required: bool) -> Result<()> {
+        require!(ctx.accounts.config.config_timelock_seconds == 0, ErrorCode::TimelockRequired);
+        
+        ctx.accounts.config.require_verification_challenge = required;
+        
+        emit!(ChallengeRequirementChanged {
+            required,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Challenge requirement updated!");
+        Ok(())
+    }
+
+    /// Pause a plot (e.g. a fallow season) without closing it
+    /// New batches are blocked while inactive; history and the account are kept
+    pub fn deactivate_farm_plot(ctx: Context<SetPlotActive>) -> Result<()> {
+        let farm_plot = &mut ctx.accounts.farm_plot;
+        require_keys_eq!(farm_plot.farmer, ctx.accounts.farmer.key(), ErrorCode::NotPlotOwner);
+        require!(farm_plot.is_active, ErrorCode::PlotAlreadyInactive);
+        
+        farm_plot.is_active = false;
+        
+        // Inactive plots drop out of the farmer's active-plot average
+        let profile = &mut ctx.accounts.farmer_profile;
+        profile.active_plots = profile.active_plots.saturating_sub(1);
+        profile.compliance_score_sum = profile
+            .compliance_score_sum
+            .saturating_sub(farm_plot.compliance_score as u64);
+        profile.refresh_average();
+        append_audit_entry(
+            &mut ctx.accounts.audit_log,
+            farm_plot.key(),
+            ctx.bumps.audit_log,
+            AuditAction::Deactivated,
+            ctx.accounts.farmer.key(),
+            [0; 32],
+        )?;
+        
+        emit!(FarmPlotDeactivated {
+            farm_plot: farm_plot.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Farm plot deactivated!");
+        Ok(())
+    }
+
+    /// Bring a paused plot back into service
+    pub fn reactivate_farm_plot(ctx: Context<SetPlotActive>) -> Result<()> {
+        let farm_plot = &mut ctx.accounts.farm_plot;
+        require_keys_eq!(farm_plot.farmer, ctx.accounts.farmer.key(), ErrorCode::NotPlotOwner);
+        require!(!farm_plot.is_active, ErrorCode::PlotAlreadyActive);
+        
+        farm_plot.is_active = true;
+        
+        let profile = &mut ctx.accounts.farmer_profile;
+        profile.active_plots = profile.active_plots.saturating_add(1);
+        profile.compliance_score_sum = profile
+            .compliance_score_sum
+            .saturating_add(farm_plot.compliance_score as u64);
+        profile.refresh_average();
+        append_audit_entry(
+            &mut ctx.accounts.audit_log,
+            farm_plot.key(),
+            ctx.bumps.audit_log,
+            AuditAction::Reactivated,
+            ctx.accounts.farmer.key(),
+            [0; 32],
+        )?;
+        
+        emit!(FarmPlotReactivated {
+            farm_plot: farm_plot.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Farm plot reactivated!");
+        Ok(())
+    }
+
+    /// Derive a processed product (e.g. cocoa butter) from a harvest batch
+    /// The derivative inherits the parent's compliance status and source plot
+    pub fn create_derivative(
+        ctx: Context<CreateDerivative>,
+        product_name: String,
+        output_weight_kg: u64,
+    ) -> Result<()> {
+        let batch = &mut ctx.accounts.harvest_batch;
+        let derivative = &mut ctx.accounts.derivative;
+        
+        require!(!batch.is_recalled(), ErrorCode::InvalidStatusTransition);
+        require!(!batch.export_locked, ErrorCode::BatchLocked);
+        require!(
+            batch.compliance_status == ComplianceStatus::Compliant,
+            ErrorCode::NonCompliantFarm
+        );
+        require!(!product_name.is_empty(), ErrorCode::InvalidProductName);
+        require_str_len(&product_name, MAX_ID_LEN, ErrorCode::InvalidProductName)?;
+        require!(output_weight_kg > 0, ErrorCode::InvalidWeight);
+        require!(output_weight_kg <= batch.traceable_weight_kg, ErrorCode::UnbackedWeight);
+        
+        derivative.parent_batch = batch.key();
+        derivative.index = batch.derivative_count;
+        derivative.product_name = product_name.clone();
+        derivative.output_weight_kg = output_weight_kg;
+        derivative.commodity_type = batch.commodity_type;
+        derivative.compliance_status = batch.compliance_status;
+        derivative.source_plot = batch.farm_plot;
+        derivative.created_at = Clock::get()?.unix_timestamp;
+        derivative.bump = ctx.bumps.derivative;
+        batch.derivative_count = batch.derivative_count.checked_add(1).ok_or(ErrorCode::ArithmeticOverflow)?;
+        
+        emit!(DerivativeCreated {
+            batch_id: batch.batch_id.clone(),
+            index: derivative.index,
+            product_name,
+            output_weight_kg,
+            timestamp: derivative.created_at,
+        });
+        
+        msg!("Derivative product created!");
+        Ok(())
+    }
+
+    /// Record one satellite pass covering several plots
+    /// remaining_accounts holds, per plot: farm plot, its (uninitialized) verification PDA,
+    /// the farmer profile, the commodity policy PDA, the plot's current verification index
+    /// segment, its audit log and the verifier's challenge PDA for the plot (left empty
+    /// when none was issued); `results` and `oracle_messages` are parallel to the plots
+    pub fn record_satellite_verification_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RecordSatelliteVerificationBatch<'info>>,
+        verification_hash: String,
+        verification_timestamp: i64,
+        confidence: u8,
+        results: Vec<bool>,
+        provider: String,
+        imagery_date: i64,
+        oracle_messages: Vec<SignedOracleMessage>,
+        verification_cost_lamports: u64,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+        require_str_len(&verification_hash, ctx.accounts.config.limits().max_hash_len as usize, ErrorCode::InvalidHash)?;
+        let verification_digest = parse_digest(&verification_hash)?;
+        require!(confidence <= 100, ErrorCode::InvalidConfidence);
+        validate_imagery_source(&provider, imagery_date)?;
+        require!(
+            !results.is_empty() && results.len() <= MAX_PLOTS_PER_VERIFICATION_BATCH,
+            ErrorCode::InvalidVerificationBatch
+        );
+        require!(
+            ctx.remaining_accounts.len() == results.len() * VERIFICATION_BATCH_ACCOUNTS_PER_PLOT,
+            ErrorCode::InvalidVerificationBatch
+        );
+        require!(oracle_messages.len() == results.len(), ErrorCode::InvalidVerificationBatch);
+        
+        let verifier = ctx.accounts.verifier.key();
+        let imagery_seed = imagery_seed(&verification_hash);
+        let payer = ctx.accounts.verifier.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let mut compliant_count: u32 = 0;
+        
+        // One Ed25519 instruction per plot, in order, directly before this one
+        let plot_count = results.len();
+        for (i, (no_deforestation, accounts)) in results
+            .iter()
+            .zip(ctx.remaining_accounts.chunks(VERIFICATION_BATCH_ACCOUNTS_PER_PLOT))
+            .enumerate()
+        {
+            let [plot_info, info, profile_info, policy_info, index_info, audit_info, challenge_info] = accounts else {
+                return err!(ErrorCode::InvalidVerificationBatch);
+            };
+            let mut farm_plot = Account::<FarmPlot>::try_from(plot_info)?;
+            let mut farmer_profile = Account::<FarmerProfile>::try_from(profile_info)?;
+            require_keys_eq!(farmer_profile.farmer, farm_plot.farmer, ErrorCode::InvalidVerificationBatch);
+            
+            let (policy_key, _) = Pubkey::find_program_address(
+                &[b"policy", &[farm_plot.commodity_type as u8]],
+                &crate::ID,
+            );
+            require_keys_eq!(*policy_info.key, policy_key, ErrorCode::InvalidPolicyAccount);
+            let policy = CommodityPolicy::load_or_default(policy_info, farm_plot.commodity_type)?;
+            
+            // The verification, index segment and audit log are created here as
+            // init / init_if_needed would
+            let plot_key = farm_plot.key();
+            let (expected, bump) = Pubkey::find_program_address(
+                &[b"verification", plot_key.as_ref(), verifier.as_ref(), &imagery_seed],
+                &crate::ID,
+            );
+            require_keys_eq!(*info.key, expected, ErrorCode::InvalidVerificationBatch);
+            require!(info.data_is_empty(), ErrorCode::VerificationAlreadyRecorded);
+            create_pda_account(
+                &payer,
+                info,
+                &system_program,
+                SATELLITE_VERIFICATION_SPACE,
+                &[b"verification", plot_key.as_ref(), verifier.as_ref(), &imagery_seed, &[bump]],
+            )?;
+            
+            let segment = farm_plot.verification_count / VERIFICATION_INDEX_CAPACITY;
+            let (index_key, index_bump) = Pubkey::find_program_address(
+                &[b"verification_index", plot_key.as_ref(), &segment.to_le_bytes()],
+                &crate::ID,
+            );
+            require_keys_eq!(*index_info.key, index_key, ErrorCode::InvalidVerificationBatch);
+            if index_info.data_is_empty() {
+                create_pda_account(
+                    &payer,
+                    index_info,
+                    &system_program,
+                    VERIFICATION_INDEX_SPACE,
+                    &[b"verification_index", plot_key.as_ref(), &segment.to_le_bytes(), &[index_bump]],
+                )?;
+            }
+            
+            let (audit_key, audit_bump) = Pubkey::find_program_address(&[b"audit", plot_key.as_ref()], &crate::ID);
+            require_keys_eq!(*audit_info.key, audit_key, ErrorCode::InvalidVerificationBatch);
+            if audit_info.data_is_empty() {
+                create_pda_account(
+                    &payer,
+                    audit_info,
+                    &system_program,
+                    AUDIT_LOG_SPACE,
+                    &[b"audit", plot_key.as_ref(), &[audit_bump]],
+                )?;
+            }
+            
+            let (challenge_key, _) = Pubkey::find_program_address(
+                &[b"challenge", plot_key.as_ref(), verifier.as_ref()],
+                &crate::ID,
+            );
+            require_keys_eq!(*challenge_info.key, challenge_key, ErrorCode::InvalidVerificationBatch);
+            let mut challenge = if challenge_info.data_is_empty() {
+                None
+            } else {
+                Some(Account::<VerificationChallenge>::try_from(challenge_info)?)
+            };
+            
+            // Freshly created accounts are zeroed, which decodes as empty records
+            let mut verification = SatelliteVerification::try_deserialize_unchecked(&mut &info.try_borrow_data()?[..])?;
+            verification.bump = bump;
+            let mut index = load_or_zeroed::<VerificationIndex>(index_info)?;
+            let mut audit_log = load_or_zeroed::<AuditLog>(audit_info)?;
+            
+            let submission = VerificationSubmission {
+                verifier,
+                verification_hash: &verification_hash,
+                verification_digest,
+                no_deforestation: *no_deforestation,
+                verification_timestamp,
+                confidence,
+                provider: &provider,
+                imagery_date,
+                verification_cost_lamports,
+                oracle_message: &oracle_messages[i],
+                oracle_message_ix: plot_count - i,
+            };
+            record_plot_verification(
+                VerificationAccounts {
+                    farm_plot: &mut farm_plot,
+                    farmer_profile: &mut farmer_profile,
+                    verification: &mut verification,
+                    verification_key: *info.key,
+                    audit_log: &mut audit_log,
+                    audit_log_bump: audit_bump,
+                    verification_index: &mut index,
+                    verification_index_bump: index_bump,
+                    registered_oracle: ctx.accounts.registered_oracle.as_deref(),
+                    oracle_nonce: &mut ctx.accounts.oracle_nonce,
+                    oracle_nonce_bump: ctx.bumps.oracle_nonce,
+                    instructions_sysvar: &ctx.accounts.instructions_sysvar,
+                    challenge: challenge.as_deref_mut(),
+                },
+                &submission,
+                &policy,
+                &ctx.accounts.config,
+            )?;
+            
+            if let Some(challenge) = challenge {
+                challenge.exit(&crate::ID)?;
+            }
+            verification.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+            index.try_serialize(&mut &mut index_info.try_borrow_mut_data()?[..])?;
+            audit_log.try_serialize(&mut &mut audit_info.try_borrow_mut_data()?[..])?;
+            farm_plot.exit(&crate::ID)?;
+            farmer_profile.exit(&crate::ID)?;
+            
+            if *no_deforestation {
+                compliant_count += 1;
+            }
+        }
+        
+        // As with a single verification, a funding pool reimburses the whole pass
+        if let Some(funding_pool) = ctx.accounts.funding_pool.as_ref() {
+            let total_cost_lamports = verification_cost_lamports
+                .checked_mul(plot_count as u64)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+            if total_cost_lamports > 0 {
+                anchor_lang::system_program::transfer(
+                    CpiContext::new(
+                        ctx.accounts.system_program.to_account_info(),
+                        anchor_lang::system_program::Transfer {
+                            from: funding_pool.to_account_info(),
+                            to: ctx.accounts.verifier.to_account_info(),
+                        },
+                    ),
+                    total_cost_lamports,
+                )?;
+            }
+        }
+        
+        emit!(SatelliteVerificationBatchRecorded {
+            verifier,
+            verification_hash,
+            plot_count: results.len() as u32,
+            compliant_count,
+            non_compliant_count: results.len() as u32 - compliant_count,
+            timestamp: verification_timestamp,
+        });
+        
+        msg!("Satellite verification batch recorded!");
+        Ok(())
+    }
+
+    /// Anchor a digest of the plot's current state for off-chain archival
+    /// Repeated checkpoints overwrite the plot's single checkpoint account
+    pub fn checkpoint_plot(ctx: Context<CheckpointPlot>) -> Result<()> {
+        let farm_plot = &ctx.accounts.farm_plot;
+        let checkpoint = &mut ctx.accounts.checkpoint;
+        
+        checkpoint.farm_plot = farm_plot.key();
+        checkpoint.digest = plot_checkpoint_digest(&farm_plot.key(), farm_plot);
+        checkpoint.version = CHECKPOINT_VERSION;
+        checkpoint.checkpointed_at = Clock::get()?.unix_timestamp;
+        checkpoint.bump = ctx.bumps.checkpoint;
+        
+        emit!(PlotCheckpointed {
+            farm_plot: checkpoint.farm_plot,
+            digest: checkpoint.digest,
+            timestamp: checkpoint.checkpointed_at,
+        });
+        
+        msg!("Plot checkpointed!");
+        Ok(())
+    }
+
+    /// Add a secondary crop to an agroforestry plot with its own area allocation
+    /// Crop allocations together can't exceed the plot's area
+    pub fn add_crop(
+        ctx: Context<AddCrop>,
+        commodity_type: CommodityType,
+        area_hectares: f64,
+    ) -> Result<()> {
+        let farm_plot = &mut ctx.accounts.farm_plot;
+        let crop = &mut ctx.accounts.crop;
+        
+        require_keys_eq!(farm_plot.farmer, ctx.accounts.farmer.key(), ErrorCode::NotPlotOwner);
+        require!(area_hectares.is_finite() && area_hectares > 0.0, ErrorCode::InvalidArea);
+        require!(commodity_type != farm_plot.commodity_type, ErrorCode::CommodityMismatch);
+        let allocated = farm_plot.allocated_crop_hectares + area_hectares;
+        require!(allocated <= farm_plot.area_hectares, ErrorCode::AreaAllocationExceeded);
+        
+        crop.farm_plot = farm_plot.key();
+        crop.commodity_type = commodity_type;
+        crop.area_hectares = area_hectares;
+        crop.harvested_weight_kg = 0;
+        crop.bump = ctx.bumps.crop;
+        farm_plot.allocated_crop_hectares = allocated;
+        
+        emit!(CropAdded {
+            farm_plot: farm_plot.key(),
+            commodity_type,
+            area_hectares,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Crop added to farm plot!");
+        Ok(())
+    }
+
+    /// A second registered oracle re-checks the plot's latest verification
+    /// If the two disagree the plot is parked at Medium risk pending a tie-breaker
+    pub fn contest_verification(
+        ctx: Context<ContestVerification>,
+        verification_hash: String,
+        no_deforestation: bool,
+        verification_timestamp: i64,
+        confidence: u8,
+        provider: String,
+        imagery_date: i64,
+        oracle_message: SignedOracleMessage,
+    ) -> Result<()> {
+        let farm_plot = &mut ctx.accounts.farm_plot;
+        let original = &ctx.accounts.original;
+        let contest = &mut ctx.accounts.contest;
+        require!(contest.verifier == Pubkey::default(), ErrorCode::VerificationAlreadyRecorded);
+        
+        require_str_len(&verification_hash, ctx.accounts.config.limits().max_hash_len as usize, ErrorCode::InvalidHash)?;
+        let verification_digest = parse_digest(&verification_hash)?;
+        require!(confidence <= 100, ErrorCode::InvalidConfidence);
+        validate_imagery_source(&provider, imagery_date)?;
+        require!(
+            verification_timestamp <= Clock::get()?.unix_timestamp + MAX_CLOCK_SKEW_SECONDS,
+            ErrorCode::VerificationInFuture
+        );
+        check_oracle_standing(Some(&ctx.accounts.registered_oracle), &ctx.accounts.config)?;
+        let policy = CommodityPolicy::load_or_default(&ctx.accounts.commodity_policy, farm_plot.commodity_type)?;
+        policy.check_verifier_tier(Some(&ctx.accounts.registered_oracle))?;
+        // Only the plot's current result can be contested, and not by its own author
+        require!(
+            original.entry_hash == farm_plot.last_verification_hash,
+            ErrorCode::NotLatestVerification
+        );
+        require_keys_neq!(original.verifier, ctx.accounts.contester.key(), ErrorCode::SameOracle);
+        require_keys_neq!(farm_plot.farmer, ctx.accounts.contester.key(), ErrorCode::FarmerCannotVerify);
+        verify_oracle_message(
+            &oracle_message,
+            &ctx.accounts.instructions_sysvar,
+            1,
+            &ctx.accounts.contester.key(),
+            &farm_plot.key(),
+            no_deforestation,
+            confidence,
+        )?;
+        ctx.accounts.oracle_nonce.consume(
+            ctx.accounts.contester.key(),
+            ctx.bumps.oracle_nonce,
+            oracle_message.message.nonce,
+        )?;
+        
+        contest.farm_plot = farm_plot.key();
+        contest.verifier = ctx.accounts.contester.key();
+        contest.verification_timestamp = verification_timestamp;
+        contest.verification_hash = verification_hash;
+        contest.verification_digest = verification_digest;
+        contest.no_deforestation = no_deforestation;
+        contest.verification_type = VerificationType::Satellite;
+        contest.bump = ctx.bumps.contest;
+        contest.baseline = farm_plot.land_cover_baseline;
+        contest.confidence = confidence;
+        contest.contests = Some(original.key());
+        contest.provider = provider;
+        contest.imagery_date = imagery_date;
+        contest.canopy_cover_bps = oracle_message.message.canopy_cover_bps;
+        contest.canopy_change_bps =
+            canopy_change_from_baseline(farm_plot, contest.canopy_cover_bps, no_deforestation)?;
+        
+        // The contest joins the chain but only moves compliance on disagreement
+        contest.prev_hash = farm_plot.last_verification_hash;
+        contest.chain_index = farm_plot.verification_count;
+        contest.entry_hash = verification_entry_hash(contest);
+        farm_plot.last_verification_hash = contest.entry_hash;
+        farm_plot.verification_count = farm_plot.verification_count.saturating_add(1);
+        
+        let disagrees = no_deforestation != original.no_deforestation;
+        if disagrees {
+            let old_score = farm_plot.compliance_score;
+            let old_risk = farm_plot.deforestation_risk;
+            set_compliance(farm_plot, i32::from(CONTESTED_COMPLIANCE_SCORE), DeforestationRisk::Medium, "contested");
+            ctx.accounts.farmer_profile.record_compliance_change(farm_plot, old_score, old_risk);
+        }
+        append_audit_entry(
+            &mut ctx.accounts.audit_log,
+            farm_plot.key(),
+            ctx.bumps.audit_log,
+            AuditAction::Contested,
+            contest.verifier,
+            contest.entry_hash,
+        )?;
+        record_in_verification_index(
+            &mut ctx.accounts.verification_index,
+            farm_plot.key(),
+            ctx.bumps.verification_index,
+            contest.key(),
+            contest,
+        )?;
+        
+        emit!(VerificationContested {
+            farm_plot: farm_plot.key(),
+            original: original.key(),
+            contest: contest.key(),
+            disagrees,
+            timestamp: verification_timestamp,
+        });
+        
+        msg!("Verification contested!");
+        Ok(())
+    }
+
+    /// Flag a perishable batch whose shelf life has passed
+    /// Anyone can crank this; delivered and recalled batches are left alone
+    pub fn mark_batch_expired(ctx: Context<MarkBatchExpired>) -> Result<()> {
+        let batch = &mut ctx.accounts.harvest_batch;
+        let now = Clock::get()?.unix_timestamp;
+        
+        let expires_at = batch.expires_at.ok_or(ErrorCode::BatchNotExpired)?;
+        require!(now > expires_at, ErrorCode::BatchNotExpired);
+        require!(
+            !batch.is_recalled()
+                && !matches!(
+                    batch.status,
+                    BatchStatus::Delivered | BatchStatus::Expired | BatchStatus::Consumed
+                ),
+            ErrorCode::InvalidStatusTransition
+        );
+        
+        batch.status = BatchStatus::Expired;
+        
+        emit!(BatchMarkedExpired {
+            batch_id: batch.batch_id.clone(),
+            expired_at: expires_at,
+            timestamp: now,
+        });
+        
+        msg!("Batch marked expired!");
+        Ok(())
+    }
+
+    /// Add an accredited certification body (Organic, Fairtrade, ...) to the registry (admin only)
+    pub fn approve_certifier(
+        ctx: Context<ApproveCertifier>,
+        certifier: Pubkey,
+        name: String,
+        accreditation_id: String,
+    ) -> Result<()> {
+        require!(!name.is_empty(), ErrorCode::InvalidCertifierDetails);
+        require!(!accreditation_id.is_empty(), ErrorCode::InvalidCertifierDetails);
+        require_str_len(&name, MAX_NAME_LEN, ErrorCode::InvalidCertifierDetails)?;
+        require_str_len(&accreditation_id, MAX_ACCREDITATION_ID_LEN, ErrorCode::InvalidCertifierDetails)?;
+        
+        let accredited = &mut ctx.accounts.accredited_certifier;
+        
+        accredited.certifier = certifier;
+        accredited.name = name;
+        accredited.accreditation_id = accreditation_id.clone();
+        accredited.approved_by = ctx.accounts.admin.key();
+        accredited.is_active = true;
+        accredited.bump = ctx.bumps.accredited_certifier;
+        
+        emit!(CertifierApprovalChanged {
+            certifier,
+            accreditation_id,
+            approved: true,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Certifier approved!");
+        Ok(())
+    }
+
+    /// Revoke a certification body's accreditation (admin only)
+    pub fn revoke_certifier(ctx: Context<RevokeCertifier>) -> Result<()> {
+        let accredited = &mut ctx.accounts.accredited_certifier;
+        
+        accredited.is_active = false;
+        
+        emit!(CertifierApprovalChanged {
+            certifier: accredited.certifier,
+            accreditation_id: accredited.accreditation_id.clone(),
+            approved: false,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Certifier revoked!");
+        Ok(())
+    }
+
+    /// Record a certification issued by an accredited body for a plot or one of its batches
+    /// `subject` is the farm plot, or the harvest batch when one is passed
+    pub fn record_certification(
+        ctx: Context<RecordCertification>,
+        subject: Pubkey,
+        cert_type: String,
+        valid_from: i64,
+        valid_until: i64,
+    ) -> Result<()> {
+        require!(!cert_type.is_empty(), ErrorCode::InvalidCertificationType);
+        require_str_len(&cert_type, MAX_ID_LEN, ErrorCode::InvalidCertificationType)?;
+        require!(valid_until > valid_from, ErrorCode::InvalidValidityWindow);
+        
+        let farm_plot_key = ctx.accounts.farm_plot.key();
+        match &ctx.accounts.harvest_batch {
+            Some(batch) => {
+                require_keys_eq!(batch.farm_plot, farm_plot_key, ErrorCode::InvalidCertificationSubject);
+                require_keys_eq!(subject, batch.key(), ErrorCode::InvalidCertificationSubject);
+            }
+            None => require_keys_eq!(subject, farm_plot_key, ErrorCode::InvalidCertificationSubject),
+        }
+        
+        let certification = &mut ctx.accounts.certification;
+        let now = Clock::get()?.unix_timestamp;
+        
+        certification.certifier = ctx.accounts.certifier.key();
+        certification.farm_plot = farm_plot_key;
+        certification.subject = subject;
+        certification.cert_type = cert_type.clone();
+        certification.valid_from = valid_from;
+        certification.valid_until = valid_until;
+        certification.recorded_at = now;
+        certification.bump = ctx.bumps.certification;
+        
+        emit!(CertificationRecorded {
+            certifier: certification.certifier,
+            farm_plot: farm_plot_key,
+            subject,
+            cert_type,
+            valid_from,
+            valid_until,
+            timestamp: now,
+        });
+        
+        msg!("Certification recorded!");
+        Ok(())
+    }
+
+    /// Register an oracle that locks stake in its vault PDA (admin and oracle both sign)
+    /// The stake can be slashed if one of its verifications is overturned
+    pub fn register_oracle_with_stake(
+        ctx: Context<RegisterOracleWithStake>,
+        stake_lamports: u64,
+        tier: u8,
+    ) -> Result<()> {
+        require!(
+            stake_lamports > 0 && stake_lamports >= ctx.accounts.config.oracle_stake_lamports,
+            ErrorCode::InsufficientOracleStake
+        );
+        
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.oracle.to_account_info(),
+                    to: ctx.accounts.stake_vault.to_account_info(),
+                },
+            ),
+            stake_lamports,
+        )?;
+        
+        let registered = &mut ctx.accounts.registered_oracle;
+        let now = Clock::get()?.unix_timestamp;
+        
+        registered.oracle = ctx.accounts.oracle.key();
+        registered.approved_by = ctx.accounts.admin.key();
+        registered.is_active = true;
+        registered.bump = ctx.bumps.registered_oracle;
+        registered.stake_remaining = stake_lamports;
+        registered.tier = tier;
+        
+        emit!(OracleApprovalChanged {
+            oracle: registered.oracle,
+            approved: true,
+            timestamp: now,
+        });
+        emit!(OracleStakeChanged {
+            oracle: registered.oracle,
+            stake_remaining: registered.stake_remaining,
+            slashed_lamports: 0,
+            timestamp: now,
+        });
+        
+        msg!("Oracle registered with stake!");
+        Ok(())
+    }
+
+    /// Withdraw stake from an oracle's vault
+    /// Active oracles must keep the configured minimum; revoked oracles can withdraw it all
+    pub fn withdraw_oracle_stake(ctx: Context<WithdrawOracleStake>, amount: u64) -> Result<()> {
+        let registered = &mut ctx.accounts.registered_oracle;
+        
+        let remaining = registered
+            .stake_remaining
+            .checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientOracleStake)?;
+        if registered.is_active {
+            require!(remaining >= ctx.accounts.config.oracle_stake_lamports, ErrorCode::InsufficientOracleStake);
+        }
+        
+        let oracle = registered.oracle;
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.stake_vault.to_account_info(),
+                    to: ctx.accounts.oracle.to_account_info(),
+                },
+                &[&[b"oracle_stake", oracle.as_ref(), &[ctx.bumps.stake_vault]]],
+            ),
+            amount,
+        )?;
+        registered.stake_remaining = remaining;
+        
+        emit!(OracleStakeChanged {
+            oracle,
+            stake_remaining: remaining,
+            slashed_lamports: 0,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Oracle stake withdrawn!");
+        Ok(())
+    }
+
+    /// Confiscate stake from an oracle whose verification was overturned (admin only)
+    /// The admin upholds a disagreeing contest; each verification can be slashed once
+    pub fn slash_oracle(ctx: Context<SlashOracle>, amount: u64) -> Result<()> {
+        let verification = &ctx.accounts.verification;
+        let contest = &ctx.accounts.contest;
+        let registered = &mut ctx.accounts.registered_oracle;
+        
+        require_keys_eq!(verification.verifier, registered.oracle, ErrorCode::InvalidSlash);
+        require!(contest.contests == Some(verification.key()), ErrorCode::InvalidSlash);
+        require!(contest.no_deforestation != verification.no_deforestation, ErrorCode::InvalidSlash);
+        require!(amount > 0, ErrorCode::InvalidSlash);
+        let remaining = registered
+            .stake_remaining
+            .checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientOracleStake)?;
+        
+        let oracle = registered.oracle;
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.stake_vault.to_account_info(),
+                    to: ctx.accounts.admin.to_account_info(),
+                },
+                &[&[b"oracle_stake", oracle.as_ref(), &[ctx.bumps.stake_vault]]],
+            ),
+            amount,
+        )?;
+        registered.stake_remaining = remaining;
+        
+        let slash = &mut ctx.accounts.oracle_slash;
+        let now = Clock::get()?.unix_timestamp;
+        slash.oracle = oracle;
+        slash.verification = verification.key();
+        slash.contest = contest.key();
+        slash.amount = amount;
+        slash.slashed_at = now;
+        slash.bump = ctx.bumps.oracle_slash;
+        
+        emit!(OracleStakeChanged {
+            oracle,
+            stake_remaining: remaining,
+            slashed_lamports: amount,
+            timestamp: now,
+        });
+        
+        msg!("Oracle slashed!");
+        Ok(())
+    }
+
+    /// Return the compact batch → plot summary consumer apps encode into a QR code
+    /// Every field is length-bounded, keeping the Borsh payload under ~120 bytes
+    pub fn get_qr_payload(ctx: Context<GetQrPayload>) -> Result<QrPayload> {
+        let batch = &ctx.accounts.harvest_batch;
+        let farm_plot = &ctx.accounts.farm_plot;
+        
+        Ok(QrPayload {
+            plot_id: farm_plot.plot_id.clone(),
+            commodity: batch.commodity_type,
+            country_code: farm_plot.country_code,
+            compliance_score: farm_plot.compliance_score,
+            batch: batch.key(),
+            dds_reference: batch.dds_reference.clone(),
+        })
+    }
+
+    /// Return the human-readable summary printed on a batch's physical label
+    pub fn get_label_payload(ctx: Context<GetQrPayload>) -> Result<LabelPayload> {
+        let batch = &ctx.accounts.harvest_batch;
+        let farm_plot = &ctx.accounts.farm_plot;
+        
+        let compliance_badge = if batch.is_recalled() {
+            "BLOCKED"
+        } else {
+            batch.compliance_status.badge()
+        };
+        
+        Ok(LabelPayload {
+            batch_id: batch.batch_id.clone(),
+            commodity_name: batch.commodity_type.name().to_string(),
+            weight_kg: batch.weight_kg,
+            farmer_name: farm_plot.farmer_name.clone(),
+            harvest_date_str: format_unix_date(batch.harvest_timestamp),
+            compliance_badge: compliance_badge.to_string(),
+        })
+    }
+
+    /// Replace a plot's polygon after a land survey correction
+    /// The superseded polygon and the reason are kept in a CoordinateHistory record
+    pub fn update_plot_coordinates(
+        ctx: Context<UpdatePlotCoordinates>,
+        coordinates: String,
+        reason: String,
+    ) -> Result<()> {
+        let farm_plot = &mut ctx.accounts.farm_plot;
+        require_keys_eq!(farm_plot.farmer, ctx.accounts.farmer.key(), ErrorCode::NotPlotOwner);
+        
+        let config = &ctx.accounts.config;
+        require_str_len(&coordinates, config.limits().max_coordinates_len as usize, ErrorCode::InvalidCoordinates)?;
+        validate_plot_geometry(&coordinates, farm_plot.area_hectares, config.smallholder_threshold())?;
+        require!(coordinates != farm_plot.coordinates, ErrorCode::CoordinatesUnchanged);
+        // Revised geometry faces the same overlap check as a checked registration
+        let bounding_box = plot_bounding_box(&coordinates).ok_or(ErrorCode::InvalidBoundingBox)?;
+        check_neighbor_overlap(&bounding_box, ctx.remaining_accounts, &farm_plot.key())?;
+        require!(!reason.is_empty(), ErrorCode::InvalidRevisionReason);
+        require_str_len(&reason, MAX_NAME_LEN, ErrorCode::InvalidRevisionReason)?;
+        
+        let history = &mut ctx.accounts.coordinate_history;
+        let now = Clock::get()?.unix_timestamp;
+        
+        history.farm_plot = farm_plot.key();
+        history.revision = farm_plot.coordinate_revision;
+        history.coordinates = std::mem::replace(&mut farm_plot.coordinates, coordinates);
+        history.bounding_box = farm_plot.bounding_box;
+        history.reason = reason.clone();
+        history.changed_by = ctx.accounts.farmer.key();
+        history.changed_at = now;
+        history.bump = ctx.bumps.coordinate_history;
+        
+        farm_plot.coordinate_revision = farm_plot
+            .coordinate_revision
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        farm_plot.bounding_box = Some(bounding_box);
+        let centroid = polygon_centroid(&farm_plot.coordinates)
+            .or_else(|| farm_plot.bounding_box.map(|bbox| bbox.center()));
+        farm_plot.centroid_lat_microdeg = centroid.map(|(lat, _)| lat);
+        farm_plot.centroid_lon_microdeg = centroid.map(|(_, lon)| lon);
+        farm_plot.metadata_hash = plot_metadata_hash(farm_plot);
+        append_audit_entry(
+            &mut ctx.accounts.audit_log,
+            farm_plot.key(),
+            ctx.bumps.audit_log,
+            AuditAction::CoordinatesRevised,
+            history.changed_by,
+            anchor_lang::solana_program::hash::hashv(&[farm_plot.coordinates.as_bytes()]).to_bytes(),
+        )?;
+        
+        emit!(PlotCoordinatesRevised {
+            farm_plot: farm_plot.key(),
+            revision: farm_plot.coordinate_revision,
+            reason,
+            timestamp: now,
+        });
+        
+        msg!("Plot coordinates revised!");
+        Ok(())
+    }
+
+    /// Aggregate hectares, average compliance and flagged plots across a cooperative
+    /// The cooperative's FarmPlot accounts are passed via remaining_accounts
+    pub fn get_cooperative_stats(ctx: Context<GetCooperativeStats>) -> Result<CooperativeStats> {
+        require!(
+            !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.len() <= MAX_PLOTS_PER_STATS_QUERY,
+            ErrorCode::InvalidPlotBatch
+        );
+        
+        let mut seen = Vec::with_capacity(ctx.remaining_accounts.len());
+        let mut total_hectares = 0.0;
+        let mut score_sum: u64 = 0;
+        let mut flagged_count: u32 = 0;
+        for info in ctx.remaining_accounts.iter() {
+            require_keys_eq!(*info.owner, crate::ID, ErrorCode::InvalidPlotBatch);
+            require!(!seen.contains(info.key), ErrorCode::InvalidPlotBatch);
+            seen.push(*info.key);
+            
+            let data = info.try_borrow_data()?;
+            let plot = FarmPlot::try_deserialize(&mut &data[..])?;
+            total_hectares += plot.area_hectares;
+            score_sum = score_sum
+                .checked_add(plot.compliance_score as u64)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+            if plot.deforestation_risk == DeforestationRisk::High {
+                flagged_count = flagged_count.checked_add(1).ok_or(ErrorCode::ArithmeticOverflow)?;
+            }
+        }
+        
+        let total_plots = seen.len() as u32;
+        Ok(CooperativeStats {
+            total_plots,
+            total_hectares,
+            avg_compliance: (score_sum / total_plots as u64) as u16,
+            flagged_count,
+        })
+    }
+
+    /// Assign the export lot number and packaging count, locking the batch's weights
+    /// Processing and derivatives are rejected afterwards; delivery updates still apply
+    pub fn finalize_for_export(
+        ctx: Context<FinalizeForExport>,
+        lot_number: String,
+        packaging_units: u32,
+    ) -> Result<()> {
+        let batch = &mut ctx.accounts.harvest_batch;
+        
+        require!(!batch.export_locked, ErrorCode::BatchLocked);
+        require!(!batch.is_recalled(), ErrorCode::InvalidStatusTransition);
+        require!(!lot_number.is_empty(), ErrorCode::InvalidLotNumber);
+        require_str_len(&lot_number, MAX_ID_LEN, ErrorCode::InvalidLotNumber)?;
+        require!(packaging_units > 0, ErrorCode::InvalidPackagingUnits);
+        
+        batch.lot_number = lot_number.clone();
+        batch.packaging_units = packaging_units;
+        batch.export_locked = true;
+        
+        emit!(BatchFinalizedForExport {
+            batch_id: batch.batch_id.clone(),
+            lot_number,
+            packaging_units,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Batch finalized for export!");
+        Ok(())
+    }
+
+    /// Turn on the config timelock (admin only)
+    /// Once set, it can only be changed through propose_config_change
+    pub fn enable_config_timelock(ctx: Context<UpdateConfig>, delay_seconds: i64) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        require!(config.config_timelock_seconds == 0, ErrorCode::TimelockRequired);
+        require!(delay_seconds > 0, ErrorCode::InvalidConfigChange);
+        
+        config.config_timelock_seconds = delay_seconds;
+        
+        msg!("Config timelock enabled!");
+        Ok(())
+    }
+
+    /// Queue a sensitive config change that can be applied once the timelock elapses
+    /// Only one change can be pending at a time (admin only)
+    pub fn propose_config_change(ctx: Context<ProposeConfigChange>, change: ConfigChange) -> Result<()> {
+        change.validate()?;
+        
+        let pending = &mut ctx.accounts.pending_change;
+        let now = Clock::get()?.unix_timestamp;
+        
+        pending.change = change;
+        pending.proposed_by = ctx.accounts.admin.key();
+        pending.proposed_at = now;
+        pending.effective_at = now
+            .checked_add(ctx.accounts.config.config_timelock_seconds)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        pending.bump = ctx.bumps.pending_change;
+        
+        emit!(ConfigChangeProposed {
+            change,
+            effective_at: pending.effective_at,
+            timestamp: now,
+        });
+        
+        msg!("Config change proposed!");
+        Ok(())
+    }
+
+    /// Apply the pending config change after its delay (admin only)
+    /// Commodity policy changes need that commodity's policy account
+    pub fn apply_config_change(ctx: Context<ApplyConfigChange>) -> Result<()> {
+        let pending = &ctx.accounts.pending_change;
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= pending.effective_at, ErrorCode::TimelockNotElapsed);
+        
+        let config = &mut ctx.accounts.config;
+        match pending.change {
+            ConfigChange::CommodityPolicy { commodity_type, params } => {
+                let policy = ctx
+                    .accounts
+                    .commodity_policy
+                    .as_mut()
+                    .ok_or(ErrorCode::InvalidPolicyAccount)?;
+                require!(policy.commodity_type == commodity_type, ErrorCode::InvalidPolicyAccount);
+                policy.set_params(params);
+            }
+            ConfigChange::StringLimits(string_limits) => {
+                config.string_limits = string_limits;
+                emit!(StringLimitsUpdated {
+                    string_limits,
+                    timestamp: now,
+                });
+            }
+            ConfigChange::SmallholderThreshold(threshold_hectares) => {
+                config.smallholder_threshold_hectares = threshold_hectares;
+            }
+            ConfigChange::OracleStakeRequirement(stake_lamports) => {
+                config.oracle_stake_lamports = stake_lamports;
+            }
+            ConfigChange::Timelock(delay_seconds) => {
+                config.config_timelock_seconds = delay_seconds;
+            }
+            ConfigChange::Paused(paused) => {
+                config.paused = paused;
+                emit!(ProgramPauseChanged {
+                    paused,
+                    timestamp: now,
+                });
+            }
+            ConfigChange::ChallengeRequired(required) => {
+                config.require_verification_challenge = required;
+                emit!(ChallengeRequirementChanged {
+                    required,
+                    timestamp: now,
+                });
+            }
+        }
+        
+        emit!(ConfigChangeApplied {
+            change: pending.change,
+            timestamp: now,
+        });
+        
+        msg!("Config change applied!");
+        Ok(())
+    }
+
+    /// Drop the pending config change without applying it (admin only)
+    pub fn cancel_config_change(_ctx: Context<CancelConfigChange>) -> Result<()> {
+        msg!("Config change cancelled!");
+        Ok(())
+    }
+
+    /// Pass legal title of a batch to a buyer; the current owner signs
+    /// Status updates (physical handling) are unaffected by who holds title
+    pub fn transfer_batch_ownership(ctx: Context<TransferBatchOwnership>, new_owner: Pubkey) -> Result<()> {
+        let batch = &mut ctx.accounts.harvest_batch;
+        
+        require_keys_eq!(batch.owner, ctx.accounts.owner.key(), ErrorCode::Unauthorized);
+        require!(!batch.is_recalled(), ErrorCode::InvalidStatusTransition);
+        require_keys_neq!(new_owner, batch.owner, ErrorCode::InvalidOwnershipTransfer);
+        
+        let previous_owner = batch.owner;
+        batch.owner = new_owner;
+        
+        emit!(BatchOwnershipTransferred {
+            batch_id: batch.batch_id.clone(),
+            previous_owner,
+            new_owner,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Batch ownership transferred!");
+        Ok(())
+    }
+
+    /// Return a plot's verifications with timestamps in [start, end], oldest first
+    /// The plot's VerificationIndex segments are passed via remaining_accounts
+    pub fn get_verifications_in_range(
+        ctx: Context<GetVerificationsInRange>,
+        start: i64,
+        end: i64,
+    ) -> Result<Vec<IndexedVerification>> {
+        require!(start <= end, ErrorCode::InvalidTimeRange);
+        let farm_plot = ctx.accounts.farm_plot.key();
+        
+        let mut seen = Vec::with_capacity(ctx.remaining_accounts.len());
+        let mut matches = Vec::new();
+        for info in ctx.remaining_accounts.iter() {
+            require_keys_eq!(*info.owner, crate::ID, ErrorCode::InvalidProvenanceAccount);
+            require!(!seen.contains(info.key), ErrorCode::InvalidProvenanceAccount);
+            seen.push(*info.key);
+            let data = info.try_borrow_data()?;
+            let index = VerificationIndex::try_deserialize(&mut &data[..])?;
+            require_keys_eq!(index.farm_plot, farm_plot, ErrorCode::InvalidProvenanceAccount);
+            matches.extend(index.entries.into_iter().filter(|entry| {
+                entry.verification_timestamp >= start && entry.verification_timestamp <= end
+            }));
+        }
+        matches.sort_by_key(|entry| entry.verification_timestamp);
+        require!(matches.len() <= MAX_RANGE_RESULTS, ErrorCode::RangeTooWide);
+        
+        Ok(matches)
+    }
+
+    /// Suspend a farmer's whole operation (admin only)
+    /// New plots and batches are blocked; existing plots, batches and DDS output are untouched
+    pub fn suspend_farmer(ctx: Context<SetFarmerSuspension>) -> Result<()> {
+        let profile = &mut ctx.accounts.farmer_profile;
+        require!(!profile.suspended, ErrorCode::FarmerSuspended);
+        
+        profile.suspended = true;
+        
+        emit!(FarmerSuspensionChanged {
+            farmer: profile.farmer,
+            suspended: true,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Farmer suspended!");
+        Ok(())
+    }
+
+    /// Lift a farmer's suspension (admin only)
+    pub fn reinstate_farmer(ctx: Context<SetFarmerSuspension>) -> Result<()> {
+        let profile = &mut ctx.accounts.farmer_profile;
+        require!(profile.suspended, ErrorCode::FarmerNotSuspended);
+        
+        profile.suspended = false;
+        
+        emit!(FarmerSuspensionChanged {
+            farmer: profile.farmer,
+            suspended: false,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Farmer reinstated!");
+        Ok(())
+    }
+
+    /// Record a processor combining several input batches into one output
+    /// remaining_accounts holds (harvest batch, its farm plot) pairs parallel to `input_weights_kg`;
+    /// the mix is flagged PendingReview if any input is below the commodity's threshold
+    pub fn record_input_mix(
+        ctx: Context<RecordInputMix>,
+        mix_id: String,
+        commodity_type: CommodityType,
+        input_weights_kg: Vec<u64>,
+    ) -> Result<()> {
+        let policy = CommodityPolicy::load_or_default(&ctx.accounts.commodity_policy, commodity_type)?;
+        
+        require!(!mix_id.is_empty(), ErrorCode::InvalidInputMix);
+        require_str_len(&mix_id, MAX_ID_LEN, ErrorCode::InvalidInputMix)?;
+        require!(
+            !input_weights_kg.is_empty() && input_weights_kg.len() <= MAX_MIX_INPUTS,
+            ErrorCode::InvalidInputMix
+        );
+        require!(
+            ctx.remaining_accounts.len() == input_weights_kg.len() * 2,
+            ErrorCode::InvalidInputMix
+        );
+        
+        let mut input_batches = Vec::with_capacity(input_weights_kg.len());
+        let mut total_weight_kg: u64 = 0;
+        let mut weighted_score_sum: u128 = 0;
+        let mut tainted_input_count: u8 = 0;
+        for (accounts, input_weight) in ctx.remaining_accounts.chunks(2).zip(input_weights_kg.iter()) {
+            let batch = Account::<HarvestBatch>::try_from(&accounts[0])?;
+            let plot = Account::<FarmPlot>::try_from(&accounts[1])?;
+            require!(!input_batches.contains(&batch.key()), ErrorCode::InvalidInputMix);
+            require_keys_eq!(batch.farm_plot, plot.key(), ErrorCode::InvalidInputMix);
+            require!(batch.commodity_type == commodity_type, ErrorCode::CommodityMismatch);
+            require!(!batch.is_recalled(), ErrorCode::InvalidStatusTransition);
+            require!(*input_weight > 0, ErrorCode::InvalidWeight);
+            require!(*input_weight <= batch.traceable_weight_kg, ErrorCode::UnbackedWeight);
+            
+            if batch.compliance_status != ComplianceStatus::Compliant
+                || plot.compliance_score < policy.min_compliance_score
+            {
+                tainted_input_count += 1;
+            }
+            total_weight_kg = total_weight_kg
+                .checked_add(*input_weight)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+            weighted_score_sum += plot.compliance_score as u128 * *input_weight as u128;
+            input_batches.push(batch.key());
+        }
+        
+        let mix = &mut ctx.accounts.input_mix;
+        mix.processor = ctx.accounts.processor.key();
+        mix.mix_id = mix_id.clone();
+        mix.commodity_type = commodity_type;
+        mix.input_batches = input_batches;
+        mix.input_weights_kg = input_weights_kg;
+        mix.total_weight_kg = total_weight_kg;
+        mix.weighted_compliance_score = (weighted_score_sum / total_weight_kg as u128) as u8;
+        mix.tainted_input_count = tainted_input_count;
+        mix.compliance_status = if tainted_input_count > 0 {
+            ComplianceStatus::PendingReview
+        } else {
+            ComplianceStatus::Compliant
+        };
+        mix.created_at = Clock::get()?.unix_timestamp;
+        mix.bump = ctx.bumps.input_mix;
+        
+        emit!(InputMixRecorded {
+            mix_id,
+            processor: mix.processor,
+            total_weight_kg,
+            weighted_compliance_score: mix.weighted_compliance_score,
+            tainted_input_count,
+            compliance_status: mix.compliance_status,
+            timestamp: mix.created_at,
+        });
+        
+        msg!("Input mix recorded!");
+        Ok(())
+    }
+
+    /// Record the settlement paid for a delivered batch, signed by the batch's owner
+    pub fn record_payment(
+        ctx: Context<RecordPayment>,
+        amount: u64,
+        currency: String,
+        paid_to: Pubkey,
+    ) -> Result<()> {
+        let batch = &ctx.accounts.harvest_batch;
+        require_keys_eq!(batch.owner, ctx.accounts.payer.key(), ErrorCode::Unauthorized);
+        require!(batch.status == BatchStatus::Delivered, ErrorCode::BatchNotDelivered);
+        require!(amount > 0, ErrorCode::InvalidPayment);
+        require!(!currency.is_empty(), ErrorCode::InvalidPayment);
+        require_str_len(&currency, MAX_CURRENCY_LEN, ErrorCode::InvalidPayment)?;
+        require!(paid_to != Pubkey::default(), ErrorCode::InvalidPayment);
+        
+        let payment = &mut ctx.accounts.payment;
+        payment.harvest_batch = batch.key();
+        payment.payer = ctx.accounts.payer.key();
+        payment.paid_to = paid_to;
+        payment.amount = amount;
+        payment.currency = currency.clone();
+        payment.paid_at = Clock::get()?.unix_timestamp;
+        payment.bump = ctx.bumps.payment;
+        
+        emit!(PaymentRecorded {
+            batch_id: batch.batch_id.clone(),
+            payer: payment.payer,
+            paid_to,
+            amount,
+            currency,
+            timestamp: payment.paid_at,
+        });
+        
+        msg!("Payment recorded!");
+        Ok(())
+    }
+
+    /// Close a plot's harvest season, clearing the harvested weight the yield check counts
+    /// Only the config admin may reset it, so a farmer can't lift their own season cap
+    pub fn reset_season(ctx: Context<ResetSeason>) -> Result<()> {
+        let farm_plot = &mut ctx.accounts.farm_plot;
+        let authority = ctx.accounts.authority.key();
+        require!(authority == ctx.accounts.config.admin, ErrorCode::Unauthorized);
+        
+        let season_harvested_kg = farm_plot.season_harvested_kg;
+        farm_plot.season_harvested_kg = 0;
+        farm_plot.season_number = farm_plot
+            .season_number
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        
+        emit!(SeasonReset {
+            plot_id: farm_plot.plot_id.clone(),
+            season_number: farm_plot.season_number,
+            season_harvested_kg,
+            reset_by: authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Season reset!");
+        Ok(())
+    }
+
+    /// Attest to a plot's current compliance state for off-chain sharing
+    /// An approved attestor signs the canonical attestation bytes in a preceding Ed25519
+    /// instruction; the program checks they match the plot's on-chain state, so buyers
+    /// can verify the returned bytes against the attestor's key without an RPC read
+    pub fn sign_attestation(
+        ctx: Context<SignAttestation>,
+        attested_at: i64,
+        signature: [u8; 64],
+    ) -> Result<Vec<u8>> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            (attested_at - now).abs() <= MAX_CLOCK_SKEW_SECONDS,
+            ErrorCode::InvalidAttestationTime
+        );
+        let attestor = &ctx.accounts.approved_attestor;
+        require!(attestor.is_active, ErrorCode::AttestorNotApproved);
+        
+        let farm_plot = &ctx.accounts.farm_plot;
+        let attestation_data = PlotAttestation {
+            attestation_version: ATTESTATION_VERSION,
+            attestor: attestor.attestor,
+            farm_plot: farm_plot.key(),
+            plot_id: farm_plot.plot_id.clone(),
+            farmer: farm_plot.farmer,
+            commodity_type: farm_plot.commodity_type,
+            country_code: farm_plot.country_code,
+            compliance_score: farm_plot.compliance_score,
+            deforestation_risk: farm_plot.deforestation_risk,
+            compliance_status: farm_plot.compliance_status,
+            is_active: farm_plot.is_active,
+            last_verified: farm_plot.last_verified,
+            verification_count: farm_plot.verification_count,
+            last_verification_hash: farm_plot.last_verification_hash,
+            attested_at,
+        };
+        let mut serialized = Vec::new();
+        attestation_data
+            .serialize(&mut serialized)
+            .map_err(|_| error!(ErrorCode::SerializationFailed))?;
+        verify_ed25519_ix(
+            &ctx.accounts.instructions_sysvar,
+            1,
+            &attestor.attestor,
+            &serialized,
+            &signature,
+            ErrorCode::InvalidAttestationSignature,
+        )?;
+        
+        let attestation = &mut ctx.accounts.attestation;
+        attestation.farm_plot = farm_plot.key();
+        attestation.attestation_hash = anchor_lang::solana_program::hash::hash(&serialized).to_bytes();
+        attestation.attestation_version = ATTESTATION_VERSION;
+        attestation.attested_at = attested_at;
+        attestation.requested_by = ctx.accounts.requester.key();
+        attestation.attestor = attestor.attestor;
+        attestation.signature = signature;
+        attestation.bump = ctx.bumps.attestation;
+        
+        emit!(AttestationSigned {
+            plot_id: farm_plot.plot_id.clone(),
+            attestation: attestation.key(),
+            attestation_hash: attestation.attestation_hash,
+            attestor: attestation.attestor,
+            signature,
+            timestamp: attested_at,
+        });
+        
+        msg!("Attestation signed!");
+        Ok(serialized)
+    }
+
+    /// Approve a key whose Ed25519 signatures attest plot state off-chain (admin only)
+    pub fn approve_attestor(ctx: Context<ApproveAttestor>, attestor: Pubkey) -> Result<()> {
+        let approved = &mut ctx.accounts.approved_attestor;
+        
+        approved.attestor = attestor;
+        approved.approved_by = ctx.accounts.admin.key();
+        approved.is_active = true;
+        approved.bump = ctx.bumps.approved_attestor;
+        
+        emit!(AttestorApprovalChanged {
+            attestor,
+            approved: true,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Attestor approved!");
+        Ok(())
+    }
+
+    /// Revoke a previously approved attestor (admin only)
+    pub fn revoke_attestor(ctx: Context<RevokeAttestor>) -> Result<()> {
+        let approved = &mut ctx.accounts.approved_attestor;
+        
+        approved.is_active = false;
+        
+        emit!(AttestorApprovalChanged {
+            attestor: approved.attestor,
+            approved: false,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Attestor revoked!");
+        Ok(())
+    }
+
+    /// Change a registered oracle's verification tier (admin only)
+    pub fn set_oracle_tier(ctx: Context<RevokeOracle>, tier: u8) -> Result<()> {
+        let registered = &mut ctx.accounts.registered_oracle;
+        let previous_tier = registered.tier;
+        registered.tier = tier;
+        
+        emit!(OracleTierChanged {
+            oracle: registered.oracle,
+            previous_tier,
+            tier,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Oracle tier updated!");
+        Ok(())
+    }
+
+    /// Blank a plot's personal data (farmer name and location) on a deletion request
+    /// Geometry and compliance are kept for traceability; the farmer or the admin may redact
+    pub fn redact_farmer_pii(ctx: Context<RedactFarmerPii>) -> Result<()> {
+        let farm_plot = &mut ctx.accounts.farm_plot;
+        let authority = ctx.accounts.authority.key();
+        require!(
+            authority == farm_plot.farmer || authority == ctx.accounts.config.admin,
+            ErrorCode::Unauthorized
+        );
+        require!(!farm_plot.pii_redacted, ErrorCode::AlreadyRedacted);
+        
+        farm_plot.farmer_name.clear();
+        farm_plot.location.clear();
+        farm_plot.pii_redacted = true;
+        // Re-anchor so the redacted off-chain metadata still verifies
+        farm_plot.metadata_hash = plot_metadata_hash(farm_plot);
+        append_audit_entry(
+            &mut ctx.accounts.audit_log,
+            farm_plot.key(),
+            ctx.bumps.audit_log,
+            AuditAction::PiiRedacted,
+            authority,
+            [0; 32],
+        )?;
+        
+        emit!(FarmerPiiRedacted {
+            farm_plot: farm_plot.key(),
+            redacted_by: authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Farmer personal data redacted!");
+        Ok(())
+    }
+
+    /// Reconstruct a batch's route from its BatchStatusUpdate accounts, oldest hop first
+    /// The updates are passed via remaining_accounts in any order
+    pub fn get_route_history(ctx: Context<GetRouteHistory>) -> Result<RouteHistory> {
+        require!(ctx.remaining_accounts.len() <= MAX_ROUTE_HOPS, ErrorCode::RangeTooWide);
+        let batch = ctx.accounts.harvest_batch.key();
+        
+        let mut seen = Vec::with_capacity(ctx.remaining_accounts.len());
+        let mut hops = Vec::with_capacity(ctx.remaining_accounts.len());
+        for info in ctx.remaining_accounts.iter() {
+            require_keys_eq!(*info.owner, crate::ID, ErrorCode::InvalidProvenanceAccount);
+            require!(!seen.contains(info.key), ErrorCode::InvalidProvenanceAccount);
+            seen.push(*info.key);
+            let data = info.try_borrow_data()?;
+            let update = BatchStatusUpdate::try_deserialize(&mut &data[..])?;
+            require_keys_eq!(update.harvest_batch, batch, ErrorCode::InvalidProvenanceAccount);
+            hops.push(RouteHop {
+                update_seq: update.update_seq,
+                status: update.new_status,
+                destination: update.destination,
+                delivered_weight_kg: update.delivered_weight_kg,
+                timestamp: update.timestamp,
+            });
+        }
+        // Updates in the same second keep the order they were recorded in
+        hops.sort_by_key(|hop| (hop.timestamp, hop.update_seq));
+        
+        Ok(RouteHistory { batch, hops })
+    }
+
+    /// Commit the merkle root of a batch's off-chain documents (invoices, certificates, photos)
+    /// The batch owner may recommit as documents are added, until the batch is export-locked
+    pub fn commit_documents(ctx: Context<CommitDocuments>, documents_merkle_root: [u8; 32]) -> Result<()> {
+        let batch = &mut ctx.accounts.harvest_batch;
+        
+        require_keys_eq!(batch.owner, ctx.accounts.owner.key(), ErrorCode::Unauthorized);
+        require!(!batch.export_locked, ErrorCode::BatchLocked);
+        require!(documents_merkle_root != [0; 32], ErrorCode::InvalidDocumentRoot);
+        
+        let previous_root = batch.documents_merkle_root;
+        batch.documents_merkle_root = documents_merkle_root;
+        
+        emit!(DocumentsCommitted {
+            batch_id: batch.batch_id.clone(),
+            previous_root,
+            documents_merkle_root,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        msg!("Documents committed!");
+        Ok(())
+    }
+
+    /// Check that a document hash is included under the batch's committed merkle root
+    pub fn verify_document_inclusion(
+        ctx: Context<VerifyDocumentInclusion>,
+        leaf: [u8; 32],
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let batch = &ctx.accounts.harvest_batch;
+        
+        require!(batch.documents_merkle_root != [0; 32], ErrorCode::InvalidDocumentRoot);
+        require!(proof.len() <= MAX_MERKLE_PROOF_LEN, ErrorCode::ProofTooLong);
+        require!(
+            merkle_root_from_proof(leaf, &proof) == batch.documents_merkle_root,
+            ErrorCode::DocumentNotIncluded
+        );
+        
+        msg!("Document inclusion verified!");
+        Ok(())
+    }
+
+    /// Single yes/no on whether a plot (and optionally one of its batches) can be
+    /// exported to the EU today, with every reason it can't
+    pub fn is_eu_exportable(ctx: Context<IsEuExportable>) -> Result<EligibilityResult> {
+        let farm_plot = &ctx.accounts.farm_plot;
+        let policy = CommodityPolicy::load_or_default(&ctx.accounts.commodity_policy, farm_plot.commodity_type)?;
+        let batch = ctx.accounts.harvest_batch.as_deref();
+        if let Some(batch) = batch {
+            require_keys_eq!(batch.farm_plot, farm_plot.key(), ErrorCode::BatchPlotMismatch);
+        }
+        
+        let failures = eu_export_failures(farm_plot, &policy, batch, Clock::get()?.unix_timestamp);
+        
+        Ok(EligibilityResult {
+            eligible: failures.is_empty(),
+            failing_reasons: failures
+                .iter()
+                .map(|failure| failure.describe().to_string())
+                .collect(),
+        })
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Bounding box of a plot's polygon, or of the circle around a point + radius plot;
+/// None when the coordinates only carry a polygon hash
+pub fn plot_bounding_box(coordinates: &str) -> Option<BoundingBox> {
+    let to_e6 = |degrees: f64| (degrees * 1e6).round() as i32;
+    let bounding_box = match parse_point_radius(coordinates) {
+        Some((lat, lon, radius_meters)) => {
+            let lat_span = f64::from(radius_meters) / METERS_PER_DEGREE_LAT;
+            // Longitude degrees shrink toward the poles; near them the box spans every longitude
+            let cos_lat = lat.to_radians().cos();
+            let lon_span = if cos_lat > 1e-6 { lat_span / cos_lat } else { 180.0 };
+            BoundingBox {
+                min_lat_e6: to_e6((lat - lat_span).max(-90.0)),
+                min_lon_e6: to_e6((lon - lon_span).max(-180.0)),
+                max_lat_e6: to_e6((lat + lat_span).min(90.0)),
+                max_lon_e6: to_e6((lon + lon_span).min(180.0)),
+            }
+        }
+        None => {
+            let vertices = parse_polygon(coordinates)?;
+            let (mut min_lat, mut min_lon) = (f64::INFINITY, f64::INFINITY);
+            let (mut max_lat, mut max_lon) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+            for (lat, lon) in vertices {
+                min_lat = min_lat.min(lat);
+                min_lon = min_lon.min(lon);
+                max_lat = max_lat.max(lat);
+                max_lon = max_lon.max(lon);
+            }
+            BoundingBox {
+                min_lat_e6: to_e6(min_lat),
+                min_lon_e6: to_e6(min_lon),
+                max_lat_e6: to_e6(max_lat),
+                max_lon_e6: to_e6(max_lon),
+            }
+        }
+    };
+    bounding_box.is_valid().then_some(bounding_box)
+}
+
+/// Returns true if the two boxes overlap by more than OVERLAP_TOLERANCE_E6 on both axes
+pub fn check_plot_overlap(a: &BoundingBox, b: &BoundingBox) -> bool {
+    let lat_overlap = (a.max_lat_e6.min(b.max_lat_e6) as i64) - (a.min_lat_e6.max(b.min_lat_e6) as i64);
+    let lon_overlap = (a.max_lon_e6.min(b.max_lon_e6) as i64) - (a.min_lon_e6.max(b.min_lon_e6) as i64);
+    lat_overlap > OVERLAP_TOLERANCE_E6 && lon_overlap > OVERLAP_TOLERANCE_E6
+}
+
+/// Rejects a plot box that overlaps any neighbor plot passed as a remaining account;
+/// the plot's own account is skipped so a revision isn't checked against itself
+pub fn check_neighbor_overlap(bounding_box: &BoundingBox, neighbors: &[AccountInfo], own_plot: &Pubkey) -> Result<()> {
+    for info in neighbors.iter().filter(|info| info.key != own_plot) {
+        require_keys_eq!(*info.owner, crate::ID, ErrorCode::InvalidNeighborAccount);
+        let data = info.try_borrow_data()?;
+        let neighbor = FarmPlot::try_deserialize(&mut &data[..])?;
+        if let Some(neighbor_box) = neighbor.bounding_box {
+            require!(
+                !check_plot_overlap(bounding_box, &neighbor_box),
+                ErrorCode::PlotOverlap
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Compiles the DDS fields for a batch from its plot and optional processing record
+/// Shared by every instruction that emits DDS data so the reports stay consistent
+pub fn build_dds_report(
+    batch: &HarvestBatch,
+    farm_plot: &FarmPlot,
+    processing_record: Option<&ProcessingRecord>,
+    verification: Option<&SatelliteVerification>,
+    payment: Option<&Payment>,
+    certifications: Vec<CertificationSummary>,
+) -> DDSReport {
+    let processing = processing_record.map(|record| ProcessingStep {
+        processing_type: record.processing_type.clone(),
+        input_weight_kg: record.input_weight_kg,
+        output_weight_kg: record.output_weight_kg,
+        yield_basis_points: record.yield_basis_points,
+        processed_at: record.processed_at,
+    });
+    
+    DDSReport {
+        batch_id: batch.batch_id.clone(),
+        plot_id: farm_plot.plot_id.clone(),
+        farmer: farm_plot.farmer,
+        coordinates: farm_plot.coordinates.clone(),
+        commodity_type: farm_plot.commodity_type,
+        harvest_timestamp: batch.harvest_timestamp,
+        weight_kg: batch.weight_kg,
+        no_deforestation_verified: farm_plot.deforestation_risk != DeforestationRisk::High,
+        compliance_score: farm_plot.compliance_score,
+        last_verified: farm_plot.last_verified,
+        registration_timestamp: farm_plot.registration_timestamp,
+        processing,
+        dds_version: DDS_VERSION,
+        area_hectares: farm_plot.area_hectares,
+        verification_count: farm_plot.verification_count,
+        deforestation_risk: farm_plot.deforestation_risk,
+        head_count: batch.head_count,
+        sustainability_attrs: batch.sustainability_attrs.clone(),
+        country_code: farm_plot.country_code,
+        external_ids: batch.external_ids.clone(),
+        due_diligence_path: if farm_plot.is_smallholder {
+            DueDiligencePath::SimplifiedDueDiligence
+        } else {
+            DueDiligencePath::Standard
+        },
+        total_verification_spend: farm_plot.total_verification_spend,
+        batch_expires_at: batch.expires_at,
+        batch_expired: batch.status == BatchStatus::Expired,
+        certifications,
+        pending_review: batch.compliance_status == ComplianceStatus::PendingReview,
+        lot_number: batch.lot_number.clone(),
+        packaging_units: batch.packaging_units,
+        verification_provider: verification.map(|verification| verification.provider.clone()),
+        imagery_date: verification.map(|verification| verification.imagery_date),
+        geometry_type: PlotGeometry::of(&farm_plot.coordinates),
+        payment_confirmed: payment.is_some(),
+    }
+}
+
+/// Reads Certification accounts from remaining_accounts, keeping those currently valid
+/// Accounts certifying anything other than this batch or its plot are rejected
+pub fn collect_active_certifications(
+    accounts: &[AccountInfo],
+    batch: Pubkey,
+    farm_plot: Pubkey,
+) -> Result<Vec<CertificationSummary>> {
+    let now = Clock::get()?.unix_timestamp;
+    let mut certifications = Vec::new();
+    for info in accounts.iter() {
+        require_keys_eq!(*info.owner, crate::ID, ErrorCode::InvalidCertificationAccount);
+        let data = info.try_borrow_data()?;
+        let certification = Certification::try_deserialize(&mut &data[..])
+            .map_err(|_| error!(ErrorCode::InvalidCertificationAccount))?;
+        require!(
+            certification.subject == batch || certification.subject == farm_plot,
+            ErrorCode::InvalidCertificationAccount
+        );
+        if certification.valid_from <= now && now <= certification.valid_until {
+            certifications.push(CertificationSummary {
+                certifier: certification.certifier,
+                cert_type: certification.cert_type,
+                batch_level: certification.subject == batch,
+                valid_from: certification.valid_from,
+                valid_until: certification.valid_until,
+            });
+        }
+    }
+    Ok(certifications)
+}
+
+/// Writes a plot's compliance score, saturating into 0..=100
+/// Every score write goes through here so weighted or penalty maths can't wrap the u8
+pub fn set_compliance_score(farm_plot: &mut FarmPlot, raw_score: i32) {
+    farm_plot.compliance_score = raw_score.clamp(0, 100) as u8;
+}
+
+/// Updates a plot's compliance score and risk, emitting ComplianceScoreChanged
+/// All compliance mutations go through here so indexers see a complete audit timeline
+pub fn set_compliance(
+    farm_plot: &mut Account<FarmPlot>,
+    raw_score: i32,
+    new_risk: DeforestationRisk,
+    reason: &str,
+) {
+    let old_score = farm_plot.compliance_score;
+    let old_risk = farm_plot.deforestation_risk;
+    
+    set_compliance_score(farm_plot, raw_score);
+    let new_score = farm_plot.compliance_score;
+    farm_plot.deforestation_risk = new_risk;
+    farm_plot.compliance_status = ComplianceStatus::for_risk(new_risk);
+    
+    if old_score != new_score || old_risk != new_risk {
+        emit!(ComplianceScoreChanged {
+            farm_plot: farm_plot.key(),
+            old_score,
+            new_score,
+            old_risk,
+            new_risk,
+            reason: reason.to_string(),
+        });
+    }
+}
+
+/// Checks that the instruction `preceding` positions before this one is an Ed25519
+/// signature verification of `message` by `pubkey` with exactly `signature`,
+/// failing with `error` otherwise
+pub fn verify_ed25519_ix(
+    ix_sysvar: &AccountInfo,
+    preceding: usize,
+    pubkey: &Pubkey,
+    message: &[u8],
+    signature: &[u8; 64],
+    error: ErrorCode,
+) -> Result<()> {
+    use anchor_lang::solana_program::sysvar::instructions::{
+        load_current_index_checked, load_instruction_at_checked,
+    };
+    
+    require!(preceding > 0, error);
+    let current_index = load_current_index_checked(ix_sysvar)?;
+    let index = (current_index as usize).checked_sub(preceding).ok_or(error)?;
+    let ix = load_instruction_at_checked(index, ix_sysvar)?;
+    require_keys_eq!(
+        ix.program_id,
+        anchor_lang::solana_program::ed25519_program::ID,
+        error
+    );
+    
+    // Layout: [num_signatures: u8, padding: u8, offsets: 7 x u16, ...data]
+    let data = &ix.data;
+    require!(data.len() >= 16 && data[0] == 1, error);
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]) as usize;
+    let signature_offset = read_u16(2);
+    let signature_ix_index = read_u16(4);
+    let pubkey_offset = read_u16(6);
+    let pubkey_ix_index = read_u16(8);
+    let message_offset = read_u16(10);
+    let message_size = read_u16(12);
+    let message_ix_index = read_u16(14);
+    
+    // All data must live inside the Ed25519 instruction itself
+    let this_ix = u16::MAX as usize;
+    require!(
+        signature_ix_index == this_ix && pubkey_ix_index == this_ix && message_ix_index == this_ix,
+        error
+    );
+    
+    let signed_pubkey = data.get(pubkey_offset..pubkey_offset + 32);
+    let signed_signature = data.get(signature_offset..signature_offset + 64);
+    let signed_message = data.get(message_offset..message_offset + message_size);
+    require!(
+        signed_pubkey == Some(pubkey.as_ref())
+            && signed_signature == Some(&signature[..])
+            && signed_message == Some(message),
+        error
+    );
+    Ok(())
+}
+
+/// Parses `lat,lon` vertex pairs (e.g. "[[5.1,-1.2],[5.2,-1.2],...]") into an open ring,
+/// or None if the string isn't a plain polygon (e.g. it only carries a polygon hash)
+pub fn parse_polygon(coordinates: &str) -> Option<Vec<(f64, f64)>> {
+    let is_polygon_char = |c: char| c.is_ascii_digit() || ".-,;[]() ".contains(c);
+    if !coordinates.chars().all(is_polygon_char) {
+        return None;
+    }
+    
+    let values = coordinates
+        .split(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+        .filter(|token| !token.is_empty())
+        .map(|token| token.parse::<f64>().ok())
+        .collect::<Option<Vec<f64>>>()?;
+    if values.len() % 2 != 0 {
+        return None;
+    }
+    let mut vertices: Vec<(f64, f64)> = values.chunks(2).map(|pair| (pair[0], pair[1])).collect();
+    // A closed ring repeats its first vertex, which would skew the average
+    if vertices.len() > 1 && vertices.first() == vertices.last() {
+        vertices.pop();
+    }
+    if vertices.len() < 3 {
+        return None;
+    }
+    Some(vertices)
+}
+
+/// Parses point + radius geometry ("point:5.1,-1.2,80") into (lat, lon, radius_meters),
+/// or None if the coordinates don't carry the point discriminator or are malformed
+pub fn parse_point_radius(coordinates: &str) -> Option<(f64, f64, u32)> {
+    let mut parts = coordinates.strip_prefix(POINT_GEOMETRY_PREFIX)?.split(',');
+    let lat = parts.next()?.trim().parse::<f64>().ok()?;
+    let lon = parts.next()?.trim().parse::<f64>().ok()?;
+    let radius_meters = parts.next()?.trim().parse::<u32>().ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((lat, lon, radius_meters))
+}
+
+/// Largest radius a point + radius plot of the given area may declare
+pub fn max_radius_for_area(area_hectares: f64) -> f64 {
+    let area_m2 = area_hectares * 10_000.0;
+    (area_m2 / std::f64::consts::PI).sqrt() * POINT_RADIUS_TOLERANCE
+}
+
+/// Checks a plot's geometry against its area. Polygons are capped by vertex count;
+/// point + radius and bare-point plots are only accepted below the smallholder threshold
+pub fn validate_plot_geometry(coordinates: &str, area_hectares: f64, smallholder_threshold: f64) -> Result<()> {
+    if let Some(vertices) = parse_polygon(coordinates) {
+        require!(vertices.len() <= max_vertices_for_area(area_hectares), ErrorCode::TooManyVertices);
+        return Ok(());
+    }
+    // Smallholders may register a single point; larger plots need a polygon
+    require!(area_hectares < smallholder_threshold, ErrorCode::PolygonRequired);
+    if coordinates.starts_with(POINT_GEOMETRY_PREFIX) {
+        let (lat, lon, radius_meters) = parse_point_radius(coordinates).ok_or(ErrorCode::InvalidCoordinates)?;
+        require!(
+            (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lon),
+            ErrorCode::InvalidCoordinates
+        );
+        require!(
+            radius_meters > 0 && f64::from(radius_meters) <= max_radius_for_area(area_hectares),
+            ErrorCode::InvalidPointRadius
+        );
+    }
+    Ok(())
+}
+
+/// Vertex-average centroid of a polygon in microdegrees; a point + radius plot's
+/// centroid is its point
+pub fn polygon_centroid(coordinates: &str) -> Option<(i32, i32)> {
+    let (lat, lon) = match parse_point_radius(coordinates) {
+        Some((lat, lon, _)) => (lat, lon),
+        None => {
+            let vertices = parse_polygon(coordinates)?;
+            let count = vertices.len() as f64;
+            let lat = vertices.iter().map(|(lat, _)| lat).sum::<f64>() / count;
+            let lon = vertices.iter().map(|(_, lon)| lon).sum::<f64>() / count;
+            (lat, lon)
+        }
+    };
+    if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lon) {
+        return None;
+    }
+    Some(((lat * 1e6).round() as i32, (lon * 1e6).round() as i32))
+}
+
+/// Vertices allowed for a plot of the given area: small plots get BASE_POLYGON_VERTICES,
+/// growing by one per HECTARES_PER_EXTRA_VERTEX up to MAX_POLYGON_VERTICES
+pub fn max_vertices_for_area(area_hectares: f64) -> usize {
+    let extra = (area_hectares / HECTARES_PER_EXTRA_VERTEX) as usize;
+    (BASE_POLYGON_VERTICES + extra).min(MAX_POLYGON_VERTICES)
+}
+
+/// Collects every reason a plot currently fails the batch-registration gate
+pub fn plot_eligibility_failures(
+    farm_plot: &FarmPlot,
+    policy: &CommodityPolicy,
+    now: i64,
+) -> Vec<EligibilityFailure> {
+    let mut failures = Vec::new();
+    if !farm_plot.is_active {
+        failures.push(EligibilityFailure::PlotInactive);
+    }
+    if farm_plot.verification_count == 0 {
+        failures.push(EligibilityFailure::NoVerificationOnRecord);
+    }
+    if farm_plot.compliance_score < policy.min_compliance_score && !policy.admits_provisionally(farm_plot) {
+        failures.push(EligibilityFailure::ScoreBelowThreshold);
+    }
+    // A High-risk detection blocks the plot whatever its blended score
+    if farm_plot.deforestation_risk == DeforestationRisk::High {
+        failures.push(EligibilityFailure::HighDeforestationRisk);
+    }
+    if farm_plot.verification_lapsed(now) {
+        failures.push(EligibilityFailure::VerificationExpired);
+    }
+    // Only smallholders may rely on a point instead of a polygon
+    if farm_plot.coordinates.is_empty()
+        || (!farm_plot.is_smallholder && PlotGeometry::of(&farm_plot.coordinates) == PlotGeometry::Point)
+    {
+        failures.push(EligibilityFailure::MissingPolygon);
+    }
+    failures
+}
+
+/// Every reason a batch itself (independent of its plot) fails the eligibility gate
+pub fn batch_eligibility_failures(batch: &HarvestBatch) -> Vec<EligibilityFailure> {
+    let mut failures = Vec::new();
+    if batch.is_recalled() {
+        failures.push(EligibilityFailure::BatchRecalled);
+    }
+    if batch.compliance_status == ComplianceStatus::NonCompliant {
+        failures.push(EligibilityFailure::BatchNonCompliant);
+    }
+    if batch.status == BatchStatus::Consumed {
+        failures.push(EligibilityFailure::BatchConsumed);
+    }
+    failures
+}
+
+/// Every reason a plot, and the batch if given, can't be exported to the EU now:
+/// the batch-registration gates plus no open review or dispute on the plot, which
+/// provisional admission would otherwise let through
+pub fn eu_export_failures(
+    farm_plot: &FarmPlot,
+    policy: &CommodityPolicy,
+    batch: Option<&HarvestBatch>,
+    now: i64,
+) -> Vec<EligibilityFailure> {
+    let mut failures = plot_eligibility_failures(farm_plot, policy, now);
+    if farm_plot.compliance_status != ComplianceStatus::Compliant {
+        failures.push(EligibilityFailure::PlotUnderReview);
+    }
+    if let Some(batch) = batch {
+        failures.extend(batch_eligibility_failures(batch));
+    }
+    failures
+}
+
+/// Checks the batch-registration gate for a plot under its commodity policy,
+/// failing with the error for the first reason found
+pub fn check_plot_eligible(farm_plot: &FarmPlot, policy: &CommodityPolicy) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    match plot_eligibility_failures(farm_plot, policy, now).first() {
+        Some(failure) => Err(error!(failure.error())),
+        None => Ok(()),
+    }
+}
+
+/// For plots with a harvest season, requires a verification of this plot recorded
+/// inside the season window; plots without a season pass unconditionally
+pub fn check_in_season_verification(
+    farm_plot: &Account<FarmPlot>,
+    verification: Option<&SatelliteVerification>,
+) -> Result<()> {
+    let Some(season) = farm_plot.harvest_season else {
+        return Ok(());
+    };
+    let in_season = verification.is_some_and(|verification| {
+        verification.farm_plot == farm_plot.key()
+            && verification.verification_timestamp >= season.start
+            && verification.verification_timestamp <= season.end
+    });
+    require!(in_season, ErrorCode::NoInSeasonVerification);
+    Ok(())
+}
+
+/// Checks a String input against its byte limit. `str::len` counts UTF-8 bytes, which
+/// is what account space is reserved in, so accented or emoji text can't overflow it
+pub fn require_str_len(value: &str, max_bytes: usize, error: ErrorCode) -> Result<()> {
+    require!(value.len() <= max_bytes, error);
+    Ok(())
+}
+
+/// Head count and ear tags are only meaningful for cattle; when tags are given
+/// there must be exactly one per head
+pub fn validate_cattle_fields(
+    commodity_type: CommodityType,
+    head_count: Option<u32>,
+    ear_tag_ids: &[String],
+) -> Result<()> {
+    if commodity_type != CommodityType::Cattle {
+        require!(
+            head_count.is_none() && ear_tag_ids.is_empty(),
+            ErrorCode::CattleFieldsNotAllowed
+        );
+        return Ok(());
+    }
+    
+    require!(ear_tag_ids.len() <= MAX_EAR_TAGS, ErrorCode::TooManyEarTags);
+    if !ear_tag_ids.is_empty() {
+        require!(
+            head_count == Some(ear_tag_ids.len() as u32),
+            ErrorCode::EarTagCountMismatch
+        );
+    }
+    for tag in ear_tag_ids {
+        require!(!tag.is_empty(), ErrorCode::InvalidEarTag);
+        require_str_len(tag, MAX_EAR_TAG_LEN, ErrorCode::InvalidEarTag)?;
+    }
+    Ok(())
+}
+
+/// The plot's score as it stands today: a verification that has outlived the
+/// commodity's validity window no longer vouches for the plot, so the score decays to 0
+pub fn effective_compliance_score(farm_plot: &FarmPlot, now: i64) -> u8 {
+    if farm_plot.verification_lapsed(now) || !farm_plot.is_active {
+        0
+    } else {
+        farm_plot.compliance_score
+    }
+}
+
+/// Checks the payer can cover `required_lamports` of rent, logging the shortfall
+pub fn require_rent_balance(payer: &AccountInfo, required_lamports: u64) -> Result<()> {
+    let available = payer.lamports();
+    if available < required_lamports {
+        msg!(
+            "Rent requires {} lamports but payer has {} (short {})",
+            required_lamports,
+            available,
+            required_lamports - available
+        );
+        return err!(ErrorCode::InsufficientRentBalance);
+    }
+    Ok(())
+}
+
+/// Serialized size of a batch attribute list
+pub fn attributes_space(attributes: &[BatchAttribute]) -> usize {
+    4 + attributes
+        .iter()
+        .map(|attribute| 4 + attribute.key.len() + 4 + attribute.value.len())
+        .sum::<usize>()
+}
+
+/// Serialized size of a single external identifier entry
+pub fn external_id_space(system: &str, id: &str) -> usize {
+    4 + system.len() + 4 + id.len()
+}
+
+/// Folds a leaf up a merkle proof, hashing each pair in sorted order so proofs
+/// need no left/right flags
+pub fn merkle_root_from_proof(leaf: [u8; 32], proof: &[[u8; 32]]) -> [u8; 32] {
+    proof.iter().fold(leaf, |node, sibling| {
+        let (first, second) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+        anchor_lang::solana_program::hash::hashv(&[&first, &second]).to_bytes()
+    })
+}
+
+/// SHA-256 over the plot's canonical identifying fields, in declaration order. Strings
+/// are length-prefixed so bytes can't shift between neighbouring fields
+pub fn plot_metadata_hash(farm_plot: &FarmPlot) -> [u8; 32] {
+    anchor_lang::solana_program::hash::hashv(&[
+        &(farm_plot.plot_id.len() as u32).to_le_bytes(),
+        farm_plot.plot_id.as_bytes(),
+        farm_plot.farmer.as_ref(),
+        &(farm_plot.farmer_name.len() as u32).to_le_bytes(),
+        farm_plot.farmer_name.as_bytes(),
+        &(farm_plot.location.len() as u32).to_le_bytes(),
+        farm_plot.location.as_bytes(),
+        &(farm_plot.coordinates.len() as u32).to_le_bytes(),
+        farm_plot.coordinates.as_bytes(),
+        &farm_plot.area_hectares.to_le_bytes(),
+        &[farm_plot.commodity_type as u8],
+        &(farm_plot.commodity_label.len() as u32).to_le_bytes(),
+        farm_plot.commodity_label.as_bytes(),
+        &farm_plot.registration_timestamp.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+pub fn is_valid_country_code(code: &[u8; 2]) -> bool {
+    ISO_COUNTRY_CODES
+        .iter()
+        .any(|row| row.as_bytes().chunks(2).any(|candidate| candidate == code))
+}
+
+/// Validates a plot registration input; shared by the single and bulk paths
+pub fn validate_plot_input(
+    input: &PlotRegistrationInput,
+    limits: &StringLimits,
+    smallholder_threshold: f64,
+) -> Result<()> {
+    require_str_len(&input.plot_id, limits.max_plot_id_len as usize, ErrorCode::PlotIdTooLong)?;
+    require_str_len(&input.farmer_name, limits.max_name_len as usize, ErrorCode::FarmerNameTooLong)?;
+    require_str_len(&input.location, limits.max_name_len as usize, ErrorCode::LocationTooLong)?;
+    require!(is_valid_country_code(&input.country_code), ErrorCode::InvalidCountryCode);
+    require_str_len(&input.coordinates, limits.max_coordinates_len as usize, ErrorCode::InvalidCoordinates)?;
+    validate_plot_geometry(&input.coordinates, input.area_hectares, smallholder_threshold)?;
+    require!(input.area_hectares > 0.0, ErrorCode::InvalidArea);
+    if let Some(season) = input.harvest_season {
+        require!(season.start < season.end, ErrorCode::InvalidHarvestSeason);
+    }
+    require_str_len(&input.commodity_label, MAX_ID_LEN, ErrorCode::CommodityLabelTooLong)?;
+    // Only `Other` carries a free-form label; the named commodities must not
+    require!(
+        (input.commodity_type == CommodityType::Other) != input.commodity_label.is_empty(),
+        ErrorCode::CommodityLabelRequired
+    );
+    Ok(())
+}
+
+/// Initializes a freshly created plot from a validated input. Fields set before
+/// registration (the bounding box from register_farm_plot_checked) are kept
+pub fn populate_farm_plot(
+    farm_plot: &mut FarmPlot,
+    input: PlotRegistrationInput,
+    farmer: Pubkey,
+    bump: u8,
+    now: i64,
+    gps_attested: bool,
+    smallholder_threshold: f64,
+    validity_seconds: i64,
+) {
+    farm_plot.plot_id = input.plot_id;
+    farm_plot.farmer = farmer;
+    farm_plot.farmer_name = input.farmer_name;
+    farm_plot.location = input.location;
+    farm_plot.coordinates = input.coordinates;
+    farm_plot.area_hectares = input.area_hectares;
+    farm_plot.is_smallholder = input.area_hectares < smallholder_threshold;
+    farm_plot.season_harvested_kg = 0;
+    farm_plot.season_number = 0;
+    farm_plot.pii_redacted = false;
+    farm_plot.total_verification_spend = 0;
+    farm_plot.batch_count = 0;
+    farm_plot.traceable_weight_kg = 0;
+    farm_plot.allocated_crop_hectares = 0.0;
+    farm_plot.last_harvest_timestamp = 0;
+    farm_plot.coordinate_revision = 0;
+    farm_plot.commodity_type = input.commodity_type;
+    farm_plot.commodity_label = input.commodity_label;
+    farm_plot.registration_timestamp = input.registration_timestamp;
+    farm_plot.deforestation_risk = DeforestationRisk::Low;
+    set_compliance_score(farm_plot, i32::from(UNVERIFIED_COMPLIANCE_SCORE));
+    farm_plot.compliance_status = ComplianceStatus::Compliant;
+    // Registering isn't a verification; last_verified stays 0 until the first one
+    farm_plot.last_verified = 0;
+    farm_plot.is_active = true;
+    farm_plot.verification_count = 0;
+    farm_plot.bump = bump;
+    farm_plot.gps_attested = gps_attested;
+    farm_plot.harvest_season = input.harvest_season;
+    farm_plot.country_code = input.country_code;
+    farm_plot.verification_expires_at = now + validity_seconds;
+    farm_plot.verification_expired = false;
+    farm_plot.metadata_hash = plot_metadata_hash(farm_plot);
+    
+    // Prefer the polygon's own vertices; fall back to the bounding box when the
+    // coordinates field only carries a polygon hash
+    let centroid = polygon_centroid(&farm_plot.coordinates)
+        .or_else(|| farm_plot.bounding_box.map(|bbox| bbox.center()));
+    farm_plot.centroid_lat_microdeg = centroid.map(|(lat, _)| lat);
+    farm_plot.centroid_lon_microdeg = centroid.map(|(_, lon)| lon);
+}
+
+/// The key of a cosigner signing alongside a registration, which must hold an active
+/// registry entry; None when no cosigner signed
+pub fn resolve_cosigner(
+    cosigner: Option<&Signer>,
+    approved_cosigner: Option<&Account<ApprovedCosigner>>,
+) -> Result<Option<Pubkey>> {
+    let Some(cosigner) = cosigner else {
+        return Ok(None);
+    };
+    let approved = approved_cosigner.ok_or(ErrorCode::CosignerRequired)?;
+    require!(
+        approved.is_active && approved.cosigner == cosigner.key(),
+        ErrorCode::CosignerRequired
+    );
+    Ok(Some(cosigner.key()))
+}
+
+/// Creates a program-owned PDA the way Anchor's `init` does: with create_account when
+/// the address is empty, or by topping up rent, then allocate and assign when someone
+/// has already sent lamports to it
+pub fn create_pda_account<'info>(
+    payer: &AccountInfo<'info>,
+    target: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    use anchor_lang::system_program::{allocate, assign, create_account, transfer};
+    use anchor_lang::system_program::{Allocate, Assign, CreateAccount, Transfer};
+    
+    let required_lamports = Rent::get()?.minimum_balance(space);
+    let current_lamports = target.lamports();
+    if current_lamports == 0 {
+        return create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount {
+                    from: payer.clone(),
+                    to: target.clone(),
+                },
+                &[signer_seeds],
+            ),
+            required_lamports,
+            space as u64,
+            &crate::ID,
+        );
+    }
+    
+    if current_lamports < required_lamports {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: target.clone(),
+                },
+            ),
+            required_lamports - current_lamports,
+        )?;
+    }
+    allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Allocate {
+                account_to_allocate: target.clone(),
+            },
+            &[signer_seeds],
+        ),
+        space as u64,
+    )?;
+    assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Assign {
+                account_to_assign: target.clone(),
+            },
+            &[signer_seeds],
+        ),
+        &crate::ID,
+    )
+}
+
+/// Maps the confidence (0-100) of a deforestation detection to a score and risk:
+/// below 30 is treated as noise, 30-70 is Medium, above 70 is High
+pub fn risk_for_confidence(confidence: u8) -> (u8, DeforestationRisk) {
+    match confidence {
+        0..=29 => (100, DeforestationRisk::Low),
+        30..=70 => (MEDIUM_RISK_COMPLIANCE_SCORE, DeforestationRisk::Medium),
+        _ => (0, DeforestationRisk::High),
+    }
+}
+
+/// Blends a verification's score into the plot's running score as an exponential
+/// moving average, scaling the step by `weight_percent` (0-100)
+pub fn blend_compliance_score(current_score: u8, verification_score: u8, weight_percent: u8) -> i32 {
+    let current = i32::from(current_score);
+    let step = (i32::from(verification_score) - current) * COMPLIANCE_EMA_ALPHA_PERCENT * i32::from(weight_percent);
+    let scale = 100 * 100;
+    // Round half away from zero and move at least one point, so the score reaches
+    // the reading instead of stalling one short of it
+    let rounded = (step + step.signum() * scale / 2) / scale;
+    current + if rounded == 0 { step.signum() } else { rounded }
+}
+
+/// Runs a plot's harvest gates (eligibility, grace period, harvest time and order,
+/// yield cap) and books the harvest onto the plot, or onto the crop allocation when
+/// it's from a secondary crop
+pub fn record_plot_harvest(
+    farm_plot: &mut FarmPlot,
+    crop: Option<&mut CropRecord>,
+    policy: &CommodityPolicy,
+    weight_kg: u64,
+    harvest_timestamp: i64,
+    backfill: bool,
+) -> Result<()> {
+    // Verify farm plot is compliant (EUDR requirement)
+    check_plot_eligible(farm_plot, policy)?;
+    // Discourage registering a plot and selling from it in one go
+    require!(
+        Clock::get()?.unix_timestamp - farm_plot.registration_timestamp >= policy.harvest_grace_seconds(farm_plot),
+        ErrorCode::PlotTooNew
+    );
+    validate_harvest_timestamp(farm_plot, harvest_timestamp)?;
+    // Batches register in harvest order unless backfilling history
+    if !backfill {
+        require!(harvest_timestamp >= farm_plot.last_harvest_timestamp, ErrorCode::HarvestOutOfOrder);
+    }
+    farm_plot.last_harvest_timestamp = farm_plot.last_harvest_timestamp.max(harvest_timestamp);
+    
+    // Reject harvests the plot (or crop allocation) couldn't plausibly have produced
+    match crop {
+        Some(crop) => {
+            let crop_weight_kg = crop
+                .harvested_weight_kg
+                .checked_add(weight_kg)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+            if let Some(capacity_kg) = policy.yield_capacity_kg(crop.area_hectares) {
+                require!(crop_weight_kg <= capacity_kg, ErrorCode::ImplausibleYield);
+            }
+            crop.harvested_weight_kg = crop_weight_kg;
+        }
+        None => {
+            let season_weight_kg = farm_plot
+                .season_harvested_kg
+                .checked_add(weight_kg)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+            if let Some(capacity_kg) = policy.yield_capacity_kg(farm_plot.area_hectares) {
+                require!(season_weight_kg <= capacity_kg, ErrorCode::ImplausibleYield);
+            }
+            farm_plot.season_harvested_kg = season_weight_kg;
+        }
+    }
+    farm_plot.batch_count = farm_plot.batch_count.saturating_add(1);
+    farm_plot.traceable_weight_kg = farm_plot
+        .traceable_weight_kg
+        .checked_add(weight_kg)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    Ok(())
+}
+
+/// A harvest can't predate its plot's registration or lie in the future
+pub fn validate_harvest_timestamp(farm_plot: &FarmPlot, harvest_timestamp: i64) -> Result<()> {
+    require!(
+        harvest_timestamp >= farm_plot.registration_timestamp,
+        ErrorCode::HarvestBeforeRegistration
+    );
+    require!(
+        harvest_timestamp <= Clock::get()?.unix_timestamp + MAX_CLOCK_SKEW_SECONDS,
+        ErrorCode::HarvestInFuture
+    );
+    Ok(())
+}
+
+/// Checks an oracle's signed message against the result it accompanies: same plot,
+/// result and confidence, not yet expired, and signed by `verifier` in the Ed25519
+/// instruction `preceding` positions before this one. The nonce is left to OracleNonce
+pub fn verify_oracle_message(
+    signed: &SignedOracleMessage,
+    ix_sysvar: &AccountInfo,
+    preceding: usize,
+    verifier: &Pubkey,
+    plot: &Pubkey,
+    no_deforestation: bool,
+    confidence: u8,
+) -> Result<()> {
+    let message = &signed.message;
+    require_keys_eq!(message.plot, *plot, ErrorCode::InvalidOracleMessage);
+    require!(
+        message.result == no_deforestation && message.confidence == confidence,
+        ErrorCode::InvalidOracleMessage
+    );
+    require!(
+        message.expires_at > Clock::get()?.unix_timestamp,
+        ErrorCode::OracleMessageExpired
+    );
+    
+    let mut message_bytes = Vec::new();
+    message
+        .serialize(&mut message_bytes)
+        .map_err(|_| error!(ErrorCode::SerializationFailed))?;
+    verify_ed25519_ix(
+        ix_sysvar,
+        preceding,
+        verifier,
+        &message_bytes,
+        &signed.signature,
+        ErrorCode::InvalidOracleMessage,
+    )
+}
+
+/// Verification PDA seed for an imagery capture, so resubmitting the same imagery
+/// derives the same address
+pub fn imagery_seed(verification_hash: &str) -> [u8; 32] {
+    anchor_lang::solana_program::hash::hashv(&[verification_hash.as_bytes()]).to_bytes()
+}
+
+/// Hash of a verification's contents chained onto its predecessor's hash
+pub fn verification_entry_hash(verification: &SatelliteVerification) -> [u8; 32] {
+    anchor_lang::solana_program::hash::hashv(&[
+        &verification.prev_hash,
+        &verification.chain_index.to_le_bytes(),
+        verification.farm_plot.as_ref(),
+        verification.verifier.as_ref(),
+        &verification.verification_timestamp.to_le_bytes(),
+        verification.verification_hash.as_bytes(),
+        &[verification.no_deforestation as u8, verification.confidence],
+    ])
+    .to_bytes()
+}
+
+/// Compares an oracle's signed canopy reading with the plot's baseline. Plots with a
+/// baseline need a reading, and a clean result can't report a loss past tolerance
+pub fn canopy_change_from_baseline(
+    farm_plot: &FarmPlot,
+    canopy_cover_bps: Option<u16>,
+    no_deforestation: bool,
+) -> Result<Option<i32>> {
+    if let Some(observed_bps) = canopy_cover_bps {
+        require!(observed_bps <= 10_000, ErrorCode::InvalidCanopyCover);
+    }
+    let Some(baseline_bps) = farm_plot.baseline_canopy_cover_bps else {
+        return Ok(None);
+    };
+    let observed_bps = canopy_cover_bps.ok_or(ErrorCode::CanopyReadingRequired)?;
+    let change_bps = i32::from(observed_bps) - i32::from(baseline_bps);
+    require!(
+        !no_deforestation || change_bps >= -MAX_CANOPY_LOSS_BPS,
+        ErrorCode::CanopyLossAgainstBaseline
+    );
+    Ok(Some(change_bps))
+}
+
+/// Rate-limits oracles per plot against the cluster clock, since the submitted
+/// timestamp is the oracle's own; the registration timestamp doesn't count
+pub fn check_verification_interval(farm_plot: &FarmPlot, policy: &CommodityPolicy, now: i64) -> Result<()> {
+    require!(
+        farm_plot.verification_count == 0
+            || now - farm_plot.last_verified >= policy.min_verification_interval_seconds,
+        ErrorCode::VerificationTooSoon
+    );
+    Ok(())
+}
+
+/// Requires a registered oracle to be active and to hold the configured stake; once
+/// staking is on, unregistered verifiers have no stake to slash and are refused
+pub fn check_oracle_standing(registered_oracle: Option<&RegisteredOracle>, config: &ProgramConfig) -> Result<()> {
+    match registered_oracle {
+        Some(registered) => {
+            require!(registered.is_active, ErrorCode::OracleNotRegistered);
+            require!(
+                registered.stake_remaining >= config.oracle_stake_lamports,
+                ErrorCode::InsufficientOracleStake
+            );
+        }
+        None => require!(config.oracle_stake_lamports == 0, ErrorCode::OracleNotRegistered),
+    }
+    Ok(())
+}
+
+/// One oracle result for one plot, as submitted to either verification instruction
+pub struct VerificationSubmission<'a> {
+    pub verifier: Pubkey,
+    pub verification_hash: &'a str,
+    pub verification_digest: [u8; 32],
+    pub no_deforestation: bool,
+    pub verification_timestamp: i64,
+    pub confidence: u8,
+    pub provider: &'a str,
+    pub imagery_date: i64,
+    pub verification_cost_lamports: u64,
+    pub oracle_message: &'a SignedOracleMessage,
+    pub oracle_message_ix: usize,       // instructions back to the message's Ed25519 check
+}
+
+/// Accounts a verification touches, whether loaded by Anchor or created by hand
+pub struct VerificationAccounts<'a, 'info> {
+    pub farm_plot: &'a mut Account<'info, FarmPlot>,
+    pub farmer_profile: &'a mut FarmerProfile,
+    pub verification: &'a mut SatelliteVerification,
+    pub verification_key: Pubkey,
+    pub audit_log: &'a mut AuditLog,
+    pub audit_log_bump: u8,
+    pub verification_index: &'a mut VerificationIndex,
+    pub verification_index_bump: u8,
+    pub registered_oracle: Option<&'a RegisteredOracle>,
+    pub oracle_nonce: &'a mut OracleNonce,
+    pub oracle_nonce_bump: u8,
+    pub instructions_sysvar: &'a AccountInfo<'info>,
+    pub challenge: Option<&'a mut VerificationChallenge>,
+}
+
+/// Records one plot's satellite result: checks the verifier, cadence, signed oracle
+/// message and challenge, fills in and chains the verification, books its cost, and
+/// logs and indexes it. Shared by the single and batch instructions so they can't drift
+pub fn record_plot_verification(
+    accounts: VerificationAccounts,
+    submission: &VerificationSubmission,
+    policy: &CommodityPolicy,
+    config: &ProgramConfig,
+) -> Result<()> {
+    let VerificationAccounts {
+        farm_plot,
+        farmer_profile,
+        verification,
+        verification_key,
+        audit_log,
+        audit_log_bump,
+        verification_index,
+        verification_index_bump,
+        registered_oracle,
+        oracle_nonce,
+        oracle_nonce_bump,
+        instructions_sysvar,
+        challenge,
+    } = accounts;
+    let plot_key = farm_plot.key();
+    
+    // A farmer vouching for their own plot isn't a verification
+    require_keys_neq!(submission.verifier, farm_plot.farmer, ErrorCode::FarmerCannotVerify);
+    // A revoked or under-staked registration can't be used even where the policy sets no tier
+    check_oracle_standing(registered_oracle, config)?;
+    policy.check_verifier_tier(registered_oracle)?;
+    // Cooldown and expiry run off the cluster clock; the oracle's timestamp can't lead it
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        submission.verification_timestamp <= now + MAX_CLOCK_SKEW_SECONDS,
+        ErrorCode::VerificationInFuture
+    );
+    check_verification_interval(farm_plot, policy, now)?;
+    
+    // The oracle's signed message must match this submission and can't be replayed
+    verify_oracle_message(
+        submission.oracle_message,
+        instructions_sysvar,
+        submission.oracle_message_ix,
+        &submission.verifier,
+        &plot_key,
+        submission.no_deforestation,
+        submission.confidence,
+    )?;
+    oracle_nonce.consume(
+        submission.verifier,
+        oracle_nonce_bump,
+        submission.oracle_message.message.nonce,
+    )?;
+    
+    // An issued challenge must be answered inside the signed message, so a captured
+    // or self-made payload can't satisfy it; the config can make challenges mandatory
+    match challenge {
+        Some(challenge) => {
+            require_keys_eq!(challenge.farm_plot, plot_key, ErrorCode::ChallengeMismatch);
+            require_keys_eq!(challenge.verifier, submission.verifier, ErrorCode::ChallengeMismatch);
+            require!(!challenge.consumed, ErrorCode::ChallengeAlreadyUsed);
+            require!(
+                challenge.nonce == submission.oracle_message.message.challenge_nonce,
+                ErrorCode::ChallengeMismatch
+            );
+            require!(now <= challenge.expires_at, ErrorCode::ChallengeExpired);
+            challenge.consumed = true;
+        }
+        None => require!(!config.require_verification_challenge, ErrorCode::ChallengeRequired),
+    }
+    
+    // Store verification data
+    verification.farm_plot = plot_key;
+    verification.verifier = submission.verifier;
+    verification.verification_timestamp = submission.verification_timestamp;
+    verification.verification_hash = submission.verification_hash.to_string();
+    verification.verification_digest = submission.verification_digest;
+    verification.no_deforestation = submission.no_deforestation;
+    verification.verification_type = VerificationType::Satellite;
+    verification.baseline = farm_plot.land_cover_baseline;
+    verification.confidence = submission.confidence;
+    verification.contests = None;
+    verification.provider = submission.provider.to_string();
+    verification.imagery_date = submission.imagery_date;
+    verification.canopy_cover_bps = submission.oracle_message.message.canopy_cover_bps;
+    verification.canopy_change_bps = canopy_change_from_baseline(
+        farm_plot,
+        verification.canopy_cover_bps,
+        submission.no_deforestation,
+    )?;
+    
+    apply_satellite_verification(farm_plot, verification, farmer_profile, policy, now);
+    
+    farm_plot.total_verification_spend = farm_plot
+        .total_verification_spend
+        .checked_add(submission.verification_cost_lamports)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    append_audit_entry(
+        audit_log,
+        plot_key,
+        audit_log_bump,
+        AuditAction::Verified,
+        verification.verifier,
+        verification.entry_hash,
+    )?;
+    record_in_verification_index(
+        verification_index,
+        plot_key,
+        verification_index_bump,
+        verification_key,
+        verification,
+    )
+}
+
+/// Deserializes a program account, or a zeroed record for one just created by hand
+pub fn load_or_zeroed<T: AccountDeserialize>(info: &AccountInfo) -> Result<T> {
+    let data = info.try_borrow_data()?;
+    if data[..8] == [0; 8] {
+        T::try_deserialize_unchecked(&mut &data[..])
+    } else {
+        T::try_deserialize(&mut &data[..])
+    }
+}
+
+/// Chains a filled-in verification onto its plot and applies the result to the
+/// plot's compliance, the farmer's aggregates and the verification expiry, which
+/// runs from `now` as recorded on chain
+pub fn apply_satellite_verification(
+    farm_plot: &mut Account<FarmPlot>,
+    verification: &mut SatelliteVerification,
+    farmer_profile: &mut FarmerProfile,
+    policy: &CommodityPolicy,
+    now: i64,
+) {
+    // Link into the plot's verification chain
+    verification.prev_hash = farm_plot.last_verification_hash;
+    verification.chain_index = farm_plot.verification_count;
+    verification.entry_hash = verification_entry_hash(verification);
+    farm_plot.last_verification_hash = verification.entry_hash;
+    
+    // Update farm compliance based on verification
+    let old_score = farm_plot.compliance_score;
+    let old_risk = farm_plot.deforestation_risk;
+    // Detections escalate by confidence so one borderline reading doesn't zero the plot
+    let (new_score, new_risk) = if verification.no_deforestation {
+        (100, DeforestationRisk::Low)
+    } else {
+        risk_for_confidence(verification.confidence)
+    };
+    // Only detections are weighted by confidence; a clean result takes the full step
+    // so a plot can always recover
+    let weight_percent = if verification.no_deforestation { 100 } else { verification.confidence };
+    // The unverified placeholder isn't history, so a plot's first reading sets the score outright
+    let blended_score = if farm_plot.verification_count == 0 {
+        i32::from(new_score)
+    } else {
+        blend_compliance_score(old_score, new_score, weight_percent)
+    };
+    set_compliance(farm_plot, blended_score, new_risk, "satellite");
+    if new_risk == DeforestationRisk::High {
+        msg!("WARNING: Deforestation detected!");
+    }
+    farm_plot.last_confidence = verification.confidence;
+    
+    farmer_profile.record_compliance_change(farm_plot, old_score, old_risk);
+    
+    farm_plot.last_verified = now;
+    farm_plot.verification_count = farm_plot.verification_count.saturating_add(1);
+    farm_plot.verification_expires_at = farm_plot.last_verified + policy.validity_window();
+    farm_plot.verification_expired = false;
+}
+
+/// Appends to a plot's audit log, claiming the log on first use (it's created
+/// with init_if_needed by whichever instruction touches the plot first)
+pub fn append_audit_entry(
+    audit_log: &mut AuditLog,
+    farm_plot: Pubkey,
+    bump: u8,
+    action: AuditAction,
+    actor: Pubkey,
+    detail_hash: [u8; 32],
+) -> Result<()> {
+    if audit_log.farm_plot == Pubkey::default() {
+        audit_log.farm_plot = farm_plot;
+        audit_log.bump = bump;
+    }
+    audit_log.append(LogEntry {
+        action,
+        actor,
+        timestamp: Clock::get()?.unix_timestamp,
+        detail_hash,
+    });
+    Ok(())
+}
+
+/// SHA-256 over a plot's state in a fixed, versioned encoding: strings are
+/// length-prefixed and every integer is little-endian, so the digest doesn't
+/// depend on the account layout. The latest verification is covered by the
+/// head of the verification hash chain
+pub fn plot_checkpoint_digest(farm_plot_key: &Pubkey, farm_plot: &FarmPlot) -> [u8; 32] {
+    anchor_lang::solana_program::hash::hashv(&[
+        &[CHECKPOINT_VERSION],
+        farm_plot_key.as_ref(),
+        &(farm_plot.plot_id.len() as u32).to_le_bytes(),
+        farm_plot.plot_id.as_bytes(),
+        farm_plot.farmer.as_ref(),
+        &(farm_plot.coordinates.len() as u32).to_le_bytes(),
+        farm_plot.coordinates.as_bytes(),
+        &farm_plot.area_hectares.to_bits().to_le_bytes(),
+        &[farm_plot.commodity_type as u8],
+        &farm_plot.country_code,
+        &farm_plot.registration_timestamp.to_le_bytes(),
+        &[farm_plot.deforestation_risk as u8, farm_plot.compliance_score, farm_plot.is_active as u8],
+        &farm_plot.last_verified.to_le_bytes(),
+        &farm_plot.verification_count.to_le_bytes(),
+        &farm_plot.last_verification_hash,
+        &farm_plot.verification_expires_at.to_le_bytes(),
+        &farm_plot.batch_count.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// Decodes a 32-byte digest given as 64 hex characters or as base58
+pub fn parse_digest(hash: &str) -> Result<[u8; 32]> {
+    if hash.len() == 64 && hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+        let mut digest = [0u8; 32];
+        for (byte, pair) in digest.iter_mut().zip(hash.as_bytes().chunks(2)) {
+            let pair = std::str::from_utf8(pair).map_err(|_| error!(ErrorCode::MalformedHash))?;
+            *byte = u8::from_str_radix(pair, 16).map_err(|_| error!(ErrorCode::MalformedHash))?;
+        }
+        return Ok(digest);
+    }
+    // Pubkey parsing is a base58 decode that insists on exactly 32 bytes
+    hash.parse::<Pubkey>()
+        .map(|key| key.to_bytes())
+        .map_err(|_| error!(ErrorCode::MalformedHash))
+}
+
+/// Formats a Unix timestamp as a UTC calendar date, "YYYY-MM-DD"
+pub fn format_unix_date(timestamp: i64) -> String {
+    // Civil-from-days conversion over the proleptic Gregorian calendar
+    let days = timestamp.div_euclid(SECONDS_PER_DAY);
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Imagery provenance on a verification: a named provider and a capture date
+/// that isn't in the future
+pub fn validate_imagery_source(provider: &str, imagery_date: i64) -> Result<()> {
+    require!(!provider.is_empty(), ErrorCode::InvalidImagerySource);
+    require_str_len(provider, MAX_PROVIDER_LEN, ErrorCode::InvalidImagerySource)?;
+    require!(
+        imagery_date <= Clock::get()?.unix_timestamp + MAX_CLOCK_SKEW_SECONDS,
+        ErrorCode::ImageryDateInFuture
+    );
+    Ok(())
+}
+
+/// Appends a verification to its plot's index segment, claiming the segment on
+/// first use (segments are created with init_if_needed like the audit log)
+pub fn record_in_verification_index(
+    index: &mut VerificationIndex,
+    farm_plot: Pubkey,
+    bump: u8,
+    verification_key: Pubkey,
+    verification: &SatelliteVerification,
+) -> Result<()> {
+    if index.farm_plot == Pubkey::default() {
+        index.farm_plot = farm_plot;
+        index.segment = verification.chain_index / VERIFICATION_INDEX_CAPACITY;
+        index.bump = bump;
+    }
+    require!(
+        index.entries.len() < VERIFICATION_INDEX_CAPACITY as usize,
+        ErrorCode::VerificationIndexFull
+    );
+    index.entries.push(IndexedVerification {
+        verification: verification_key,
+        verification_timestamp: verification.verification_timestamp,
+    });
+    Ok(())
+}
+
+// ============================================================================
+// Account Structures
+// ============================================================================
+
+#[account]
+pub struct FarmPlot {
+    pub plot_id: String,                // max 32 bytes
+    pub farmer: Pubkey,
+    pub farmer_name: String,            // max 64 bytes
+    pub location: String,               // max 64 bytes
+    pub coordinates: String,            // max MAX_COORDINATES_LEN bytes
+    pub area_hectares: f64,
+    pub commodity_type: CommodityType,
+    pub registration_timestamp: i64,
+    pub deforestation_risk: DeforestationRisk,
+    pub compliance_score: u8,
+    pub last_verified: i64,
+    pub is_active: bool,
+    pub bump: u8,
+    pub verification_count: u32,
+    pub bounding_box: Option<BoundingBox>,
+    pub land_cover_baseline: Option<Pubkey>,
+    pub commodity_label: String,        // max 32, only set for CommodityType::Other
+    pub gps_attested: bool,
+    pub centroid_lat_microdeg: Option<i32>,
+    pub centroid_lon_microdeg: Option<i32>,
+    pub harvest_season: Option<HarvestSeason>,
+    pub metadata_hash: [u8; 32],
+    pub country_code: [u8; 2],          // ISO 3166-1 alpha-2
+    pub verification_expires_at: i64,
+    pub verification_expired: bool,     // set by mark_expired, cleared by re-verification
+    pub last_confidence: u8,            // detection confidence of the latest verification
+    pub last_verification_hash: [u8; 32], // head of the verification hash chain
+    pub is_smallholder: bool,           // below the smallholder area threshold at registration
+    pub season_harvested_kg: u64,       // this season's harvest counted against yield capacity
+    pub cosigned_by: Option<Pubkey>,    // approved cosigner on the registration, if any
+    pub total_verification_spend: u64,  // lamports spent on verifications of this plot
+    pub batch_count: u32,               // harvest batches registered directly against this plot
+    pub traceable_weight_kg: u64,       // total weight of batches registered against this plot
+    pub allocated_crop_hectares: f64,   // area allocated to secondary crops via add_crop
+    pub last_harvest_timestamp: i64,    // latest harvest registered; new batches can't precede it
+    pub coordinate_revision: u16,       // number of survey corrections; history in CoordinateHistory
+    pub compliance_status: ComplianceStatus,    // follows deforestation_risk, kept in sync by set_compliance
+    pub season_number: u16,             // seasons closed by reset_season
+    pub pii_redacted: bool,             // farmer_name and location blanked on a deletion request
+    pub baseline_canopy_cover_bps: Option<u16>, // copied from the LandCoverBaseline for comparisons
+}
+
+impl FarmPlot {
+    /// Whether the latest verification has outlived its commodity's validity window,
+    /// fixed when the verification was recorded
+    pub fn verification_lapsed(&self, now: i64) -> bool {
+        now > self.verification_expires_at
+    }
+}
+
+/// Harvest season window; only verifications inside it count toward the season's DDS
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct HarvestSeason {
+    pub start: i64,
+    pub end: i64,
+}
+
+/// Polygon bounding box in microdegrees (degrees * 1e6)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct BoundingBox {
+    pub min_lat_e6: i32,
+    pub min_lon_e6: i32,
+    pub max_lat_e6: i32,
+    pub max_lon_e6: i32,
+}
+
+impl BoundingBox {
+    pub fn is_valid(&self) -> bool {
+        self.min_lat_e6 <= self.max_lat_e6
+            && self.min_lon_e6 <= self.max_lon_e6
+            && self.min_lat_e6 >= -90_000_000
+            && self.max_lat_e6 <= 90_000_000
+            && self.min_lon_e6 >= -180_000_000
+            && self.max_lon_e6 <= 180_000_000
+    }
+    
+    pub fn center(&self) -> (i32, i32) {
+        (
+            ((self.min_lat_e6 as i64 + self.max_lat_e6 as i64) / 2) as i32,
+            ((self.min_lon_e6 as i64 + self.max_lon_e6 as i64) / 2) as i32,
+        )
+    }
+}
+
+#[account]
+pub struct HarvestBatch {
+    pub batch_id: String,
+    pub farm_plot: Pubkey,
+    pub farmer: Pubkey,
+    pub weight_kg: u64,
+    pub harvest_timestamp: i64,
+    pub commodity_type: CommodityType,
+    pub status: BatchStatus,
+    pub compliance_status: ComplianceStatus,
+    pub destination: String,
+    pub bump: u8,
+    pub dds_reference: String,          // max 64, empty until the DDS is recorded
+    pub remaining_weight_kg: u64,       // weight not yet delivered
+    pub source_plots: Vec<Pubkey>,      // max MAX_SOURCE_PLOTS, empty for single-plot batches
+    pub source_weights_kg: Vec<u64>,    // parallel to source_plots
+    pub pre_recall_status: Option<BatchStatus>,
+    pub recall_reason: String,          // max 64
+    pub head_count: Option<u32>,        // cattle only
+    pub ear_tag_ids: Vec<String>,       // cattle only, one per head, max 24 bytes each
+    pub sustainability_attrs: Vec<BatchAttribute>,
+    pub reported_weight: u64,           // weight as entered, in reported_unit
+    pub reported_unit: WeightUnit,
+    pub update_seq: u32,                // next BatchStatusUpdate sequence number
+    pub external_ids: Vec<ExternalId>,  // at most one per ALLOWED_EXTERNAL_SYSTEMS entry
+    pub derivative_count: u32,          // next DerivativeProduct index
+    pub traceable_weight_kg: u64,       // weight backed by registered harvest, net of processing losses
+    pub expires_at: Option<i64>,        // shelf life end for perishable commodities
+    pub lot_number: String,             // max 32, set by finalize_for_export
+    pub packaging_units: u32,           // bags, crates or head shipped under the lot number
+    pub export_locked: bool,            // finalized for export; weights can no longer change
+    pub owner: Pubkey,                  // legal title holder; starts as the farmer, separate from who handles the goods
+    pub documents_merkle_root: [u8; 32], // root over off-chain document hashes; zero until committed
+    pub recall_requested_by: Option<Pubkey>, // farmer or holder behind the pending recall
+    pub processing_count: u32,          // next ProcessingRecord step
+}
+
+impl HarvestBatch {
+    pub fn is_recalled(&self) -> bool {
+        matches!(self.status, BatchStatus::RecallRequested | BatchStatus::Recalled)
+    }
+    
+    /// Moves a batch with no traceable weight left to the terminal Consumed status;
+    /// returns whether it changed
+    pub fn consume_if_empty(&mut self) -> bool {
+        if self.traceable_weight_kg > 0 || self.status == BatchStatus::Consumed {
+            return false;
+        }
+        self.remaining_weight_kg = 0;
+        self.status = BatchStatus::Consumed;
+        true
+    }
+}
+
+#[account]
+pub struct SatelliteVerification {
+    pub farm_plot: Pubkey,
+    pub verifier: Pubkey,
+    pub verification_timestamp: i64,
+    pub verification_hash: String,
+    pub no_deforestation: bool,
+    pub verification_type: VerificationType,
+    pub bump: u8,
+    pub baseline: Option<Pubkey>,
+    pub confidence: u8,
+    pub prev_hash: [u8; 32],            // zero for a plot's first verification
+    pub chain_index: u32,
+    pub entry_hash: [u8; 32],
+    pub verification_digest: [u8; 32],  // verification_hash decoded from hex or base58
+    pub contests: Option<Pubkey>,       // verification this one was submitted against, if any
+    pub provider: String,               // imagery provider, max 32
+    pub imagery_date: i64,              // capture time of the imagery the check used
+    pub canopy_cover_bps: Option<u16>,  // canopy cover the oracle observed, if reported
+    pub canopy_change_bps: Option<i32>, // observed minus the plot's baseline, if it has one
+}
+
+#[account]
+pub struct ProcessingRecord {
+    pub harvest_batch: Pubkey,
+    pub processing_type: String,        // max MAX_PROCESSING_TYPE_LEN
+    pub input_weight_kg: u64,
+    pub output_weight_kg: u64,
+    pub yield_basis_points: u16,
+    pub processed_at: i64,
+    pub bump: u8,
+    pub step: u32,                      // position in the batch's processing chain, from 0
+}
+
+#[account]
+pub struct DerivativeProduct {
+    pub parent_batch: Pubkey,
+    pub index: u32,
+    pub product_name: String,           // max 32
+    pub output_weight_kg: u64,
+    pub commodity_type: CommodityType,
+    pub compliance_status: ComplianceStatus,  // inherited from the parent batch
+    pub source_plot: Pubkey,            // inherited from the parent batch
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct StorageLot {
+    pub lot_id: String,                 // max 32
+    pub operator: Pubkey,
+    pub commodity_type: CommodityType,
+    pub total_weight_kg: u64,
+    pub batch_count: u32,
+    pub blended_status: ComplianceStatus,
+    pub bump: u8,
+    pub withdrawal_count: u32,          // next LotWithdrawal index
+}
+
+#[account]
+pub struct LotDeposit {
+    pub storage_lot: Pubkey,
+    pub harvest_batch: Pubkey,
+    pub weight_kg: u64,
+    pub compliance_status: ComplianceStatus,
+    pub deposited_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct LotWithdrawal {
+    pub storage_lot: Pubkey,
+    pub index: u32,
+    pub weight_kg: u64,
+    pub blended_status: ComplianceStatus, // lot's status when the goods left
+    pub destination: String,            // max MAX_DESTINATION_LEN
+    pub operator: Pubkey,
+    pub withdrawn_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct VerificationChallenge {
+    pub farm_plot: Pubkey,
+    pub verifier: Pubkey,
+    pub nonce: [u8; 32],
+    pub issued_at: i64,
+    pub expires_at: i64,
+    pub consumed: bool,
+    pub bump: u8,
+}
+
+#[account]
+pub struct LandCoverBaseline {
+    pub farm_plot: Pubkey,
+    pub recorded_by: Pubkey,
+    pub canopy_cover_bps: u16,
+    pub land_cover_hash: String,        // max 64
+    pub observed_timestamp: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct ProgramConfig {
+    pub admin: Pubkey,
+    pub bump: u8,
+    pub string_limits: StringLimits,
+    pub smallholder_threshold_hectares: f64,    // 0 means DEFAULT_SMALLHOLDER_THRESHOLD_HECTARES
+    pub oracle_stake_lamports: u64,     // stake a registered oracle must hold; 0 disables staking
+    pub config_timelock_seconds: i64,   // delay on sensitive changes; 0 lets the admin set them directly
+    pub paused: bool,                   // blocks registrations, status updates and verifications
+    pub require_verification_challenge: bool,   // every verification must answer an issued challenge
+}
+
+impl ProgramConfig {
+    pub fn smallholder_threshold(&self) -> f64 {
+        if self.smallholder_threshold_hectares > 0.0 {
+            self.smallholder_threshold_hectares
+        } else {
+            DEFAULT_SMALLHOLDER_THRESHOLD_HECTARES
+        }
+    }
+    
+    /// Effective string limits; unset (zero) limits fall back to the reserved ceiling
+    pub fn limits(&self) -> StringLimits {
+        let or_ceiling = |configured: u16, ceiling: usize| {
+            if configured == 0 { ceiling as u16 } else { configured }
+        };
+        StringLimits {
+            max_plot_id_len: or_ceiling(self.string_limits.max_plot_id_len, MAX_ID_LEN),
+            max_batch_id_len: or_ceiling(self.string_limits.max_batch_id_len, MAX_ID_LEN),
+            max_name_len: or_ceiling(self.string_limits.max_name_len, MAX_NAME_LEN),
+            max_coordinates_len: or_ceiling(self.string_limits.max_coordinates_len, MAX_COORDINATES_LEN),
+            max_destination_len: or_ceiling(self.string_limits.max_destination_len, MAX_DESTINATION_LEN),
+            max_hash_len: or_ceiling(self.string_limits.max_hash_len, MAX_HASH_LEN),
+        }
+    }
+}
+
+/// Admin-configurable byte limits for String inputs. Each is capped at the
+/// compile-time ceiling, since account space is reserved for the ceiling
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct StringLimits {
+    pub max_plot_id_len: u16,
+    pub max_batch_id_len: u16,
+    pub max_name_len: u16,
+    pub max_coordinates_len: u16,
+    pub max_destination_len: u16,
+    pub max_hash_len: u16,
+}
+
+impl StringLimits {
+    pub fn within_ceilings(&self) -> bool {
+        self.max_plot_id_len as usize <= MAX_ID_LEN
+            && self.max_batch_id_len as usize <= MAX_ID_LEN
+            && self.max_name_len as usize <= MAX_NAME_LEN
+            && self.max_coordinates_len as usize <= MAX_COORDINATES_LEN
+            && self.max_destination_len as usize <= MAX_DESTINATION_LEN
+            && self.max_hash_len as usize <= MAX_HASH_LEN
+    }
+}
+
+#[account]
+pub struct CommodityPolicy {
+    pub commodity_type: CommodityType,
+    pub min_compliance_score: u8,
+    pub verification_validity_seconds: i64, // 0 uses the commodity's default window
+    pub bump: u8,
+    pub min_verification_interval_seconds: i64, // 0 disables the re-verification cooldown
+    pub max_yield_kg_per_hectare: u64,  // 0 disables the yield plausibility check
+    pub requires_cosigner: bool,        // plot registration needs an approved cosigner
+    pub min_age_before_harvest_seconds: i64,    // plot age required before its first batch
+    pub smallholder_min_age_before_harvest_seconds: i64,
+    pub allow_provisional_medium_risk: bool,    // Medium-risk plots below the score register as PendingReview
+    pub verifier_tier_required: u8,     // minimum registered oracle tier; 0 accepts any verifier
+}
+
+impl CommodityPolicy {
+    /// How long a verification of this commodity stays valid
+    pub fn validity_window(&self) -> i64 {
+        if self.verification_validity_seconds > 0 {
+            self.verification_validity_seconds
+        } else {
+            self.commodity_type.default_validity_seconds()
+        }
+    }
+    
+    /// Grace period a plot must age before registering batches
+    pub fn harvest_grace_seconds(&self, farm_plot: &FarmPlot) -> i64 {
+        if farm_plot.is_smallholder {
+            self.smallholder_min_age_before_harvest_seconds
+        } else {
+            self.min_age_before_harvest_seconds
+        }
+    }
+    
+    /// Most weight a plot of this size can yield in a season, if the policy caps it
+    pub fn yield_capacity_kg(&self, area_hectares: f64) -> Option<u64> {
+        if self.max_yield_kg_per_hectare == 0 {
+            return None;
+        }
+        Some((area_hectares * self.max_yield_kg_per_hectare as f64) as u64)
+    }
+    
+    /// Whether a plot below the score threshold may still register batches for review
+    pub fn admits_provisionally(&self, farm_plot: &FarmPlot) -> bool {
+        self.allow_provisional_medium_risk && farm_plot.deforestation_risk == DeforestationRisk::Medium
+    }
+    
+    /// Compliance status a new batch from this plot registers under: the plot's own
+    /// status, held at PendingReview at least when admitted provisionally
+    pub fn batch_compliance_status(&self, farm_plot: &FarmPlot) -> ComplianceStatus {
+        if farm_plot.compliance_score < self.min_compliance_score {
+            farm_plot.compliance_status.weakest(ComplianceStatus::PendingReview)
+        } else {
+            farm_plot.compliance_status
+        }
+    }
+    
+    /// Requires the verifier to be a registered oracle of at least the commodity's tier
+    /// when the policy sets one; otherwise any verifier may submit
+    pub fn check_verifier_tier(&self, registered_oracle: Option<&RegisteredOracle>) -> Result<()> {
+        if self.verifier_tier_required == 0 {
+            return Ok(());
+        }
+        let registered = registered_oracle.ok_or(ErrorCode::InsufficientVerifierTier)?;
+        require!(
+            registered.is_active && registered.tier >= self.verifier_tier_required,
+            ErrorCode::InsufficientVerifierTier
+        );
+        Ok(())
+    }
+    
+    pub fn set_params(&mut self, params: PolicyParams) {
+        self.min_compliance_score = params.min_compliance_score;
+        self.verification_validity_seconds = params.verification_validity_seconds;
+        self.min_verification_interval_seconds = params.min_verification_interval_seconds;
+        self.max_yield_kg_per_hectare = params.max_yield_kg_per_hectare;
+        self.requires_cosigner = params.requires_cosigner;
+        self.min_age_before_harvest_seconds = params.min_age_before_harvest_seconds;
+        self.smallholder_min_age_before_harvest_seconds = params.smallholder_min_age_before_harvest_seconds;
+        self.allow_provisional_medium_risk = params.allow_provisional_medium_risk;
+        self.verifier_tier_required = params.verifier_tier_required;
+    }
+    
+    /// Whether switching to `params` would relax any check this policy makes; zero
+    /// windows and caps are compared as what they stand for (default window, no cap)
+    pub fn is_loosened_by(&self, params: &PolicyParams) -> bool {
+        let mut proposed = self.clone();
+        proposed.set_params(*params);
+        let yield_cap = |policy: &CommodityPolicy| match policy.max_yield_kg_per_hectare {
+            0 => u64::MAX,
+            cap => cap,
+        };
+        proposed.min_compliance_score < self.min_compliance_score
+            || proposed.validity_window() > self.validity_window()
+            || proposed.min_verification_interval_seconds < self.min_verification_interval_seconds
+            || yield_cap(&proposed) > yield_cap(self)
+            || (self.requires_cosigner && !proposed.requires_cosigner)
+            || proposed.min_age_before_harvest_seconds < self.min_age_before_harvest_seconds
+            || proposed.smallholder_min_age_before_harvest_seconds < self.smallholder_min_age_before_harvest_seconds
+            || (proposed.allow_provisional_medium_risk && !self.allow_provisional_medium_risk)
+            || proposed.verifier_tier_required < self.verifier_tier_required
+    }
+    
+    /// The policy that applies to a commodity whose policy PDA was never set
+    pub fn default_for(commodity_type: CommodityType) -> CommodityPolicy {
+        CommodityPolicy {
+            commodity_type,
+            min_compliance_score: DEFAULT_MIN_COMPLIANCE_SCORE,
+            verification_validity_seconds: 0,
+            bump: 0,
+            min_verification_interval_seconds: 0,
+            max_yield_kg_per_hectare: 0,
+            requires_cosigner: false,
+            min_age_before_harvest_seconds: 0,
+            smallholder_min_age_before_harvest_seconds: 0,
+            allow_provisional_medium_risk: false,
+            verifier_tier_required: DEFAULT_VERIFIER_TIER_REQUIRED,
+        }
+    }
+    
+    /// Reads the policy PDA, falling back to defaults if it was never set
+    pub fn load_or_default(info: &AccountInfo, commodity_type: CommodityType) -> Result<CommodityPolicy> {
+        if info.data_is_empty() {
+            return Ok(CommodityPolicy::default_for(commodity_type));
+        }
+        require_keys_eq!(*info.owner, crate::ID, ErrorCode::InvalidPolicyAccount);
+        let data = info.try_borrow_data()?;
+        CommodityPolicy::try_deserialize(&mut &data[..])
+    }
+}
+
+/// A commodity policy's tunable checks, as set directly or through a timelocked proposal
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub struct PolicyParams {
+    pub min_compliance_score: u8,
+    pub verification_validity_seconds: i64,
+    pub min_verification_interval_seconds: i64,
+    pub max_yield_kg_per_hectare: u64,
+    pub requires_cosigner: bool,
+    pub min_age_before_harvest_seconds: i64,
+    pub smallholder_min_age_before_harvest_seconds: i64,
+    pub allow_provisional_medium_risk: bool,
+    pub verifier_tier_required: u8,
+}
+
+impl PolicyParams {
+    pub fn validate(&self) -> Result<()> {
+        require!(self.min_compliance_score <= 100, ErrorCode::InvalidComplianceScore);
+        require!(self.verification_validity_seconds >= 0, ErrorCode::InvalidValidityWindow);
+        require!(self.min_verification_interval_seconds >= 0, ErrorCode::InvalidValidityWindow);
+        require!(
+            self.min_age_before_harvest_seconds >= 0 && self.smallholder_min_age_before_harvest_seconds >= 0,
+            ErrorCode::InvalidValidityWindow
+        );
+        Ok(())
+    }
+}
+
+#[account]
+pub struct FarmerProfile {
+    pub farmer: Pubkey,
+    pub total_plots: u32,
+    pub active_plots: u32,
+    pub avg_compliance_score: u16,      // mean over active plots
+    pub deforestation_flags: u32,
+    pub compliance_score_sum: u64,      // running sum backing the average
+    pub bump: u8,
+    pub suspended: bool,                // blocks new plots and batches; existing records stay usable
+}
+
+impl FarmerProfile {
+    /// Folds a plot's compliance change into the aggregates; call after the plot is updated
+    pub fn record_compliance_change(&mut self, farm_plot: &FarmPlot, old_score: u8, old_risk: DeforestationRisk) {
+        if farm_plot.is_active {
+            self.apply_score_change(old_score, farm_plot.compliance_score);
+        }
+        if old_risk != DeforestationRisk::High && farm_plot.deforestation_risk == DeforestationRisk::High {
+            self.deforestation_flags = self.deforestation_flags.saturating_add(1);
+        }
+    }
+    
+    /// Swaps one active plot's old score for its new one in the running average
+    pub fn apply_score_change(&mut self, old_score: u8, new_score: u8) {
+        self.compliance_score_sum = self
+            .compliance_score_sum
+            .saturating_sub(old_score as u64)
+            .saturating_add(new_score as u64);
+        self.refresh_average();
+    }
+    
+    pub fn refresh_average(&mut self) {
+        self.avg_compliance_score = if self.active_plots == 0 {
+            0
+        } else {
+            (self.compliance_score_sum / self.active_plots as u64) as u16
+        };
+    }
+}
+
+/// Per-plot ring buffer of state changes; once AUDIT_LOG_CAPACITY entries are
+/// stored, each new entry overwrites the oldest
+#[account]
+pub struct AuditLog {
+    pub farm_plot: Pubkey,
+    pub entries: Vec<LogEntry>,         // max AUDIT_LOG_CAPACITY
+    pub total_entries: u32,             // entries ever appended; the next slot is total % capacity
+    pub bump: u8,
+}
+
+impl AuditLog {
+    pub fn append(&mut self, entry: LogEntry) {
+        if self.entries.len() < AUDIT_LOG_CAPACITY {
+            self.entries.push(entry);
+        } else {
+            let slot = self.total_entries as usize % AUDIT_LOG_CAPACITY;
+            self.entries[slot] = entry;
+        }
+        self.total_entries = self.total_entries.saturating_add(1);
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LogEntry {
+    pub action: AuditAction,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub detail_hash: [u8; 32],          // hash of the action's details, zero when there are none
+}
+
+#[account]
+pub struct ApprovedDevice {
+    pub device: Pubkey,
+    pub approved_by: Pubkey,
+    pub is_active: bool,
+    pub bump: u8,
+}
+
+#[account]
+pub struct RegisteredOracle {
+    pub oracle: Pubkey,
+    pub approved_by: Pubkey,
+    pub is_active: bool,
+    pub bump: u8,
+    pub stake_remaining: u64,           // lamports held in the oracle's stake vault
+    pub tier: u8,                       // verification authority, e.g. 1 community, 2 accredited, 3 government
+}
+
+#[account]
+pub struct ApprovedCosigner {
+    pub cosigner: Pubkey,
+    pub approved_by: Pubkey,
+    pub is_active: bool,
+    pub bump: u8,
+}
+
+#[account]
+pub struct DDSRecord {
+    pub harvest_batch: Pubkey,
+    pub farm_plot: Pubkey,
+    pub batch_id: String,               // max 32
+    pub report_hash: [u8; 32],
+    pub compliance_score: u8,
+    pub dds_version: u8,
+    pub recorded_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct BatchStatusUpdate {
+    pub harvest_batch: Pubkey,
+    pub update_seq: u32,
+    pub previous_status: BatchStatus,
+    pub new_status: BatchStatus,
+    pub destination: String,    // max 64
+    pub delivered_weight_kg: Option<u64>,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct PlotCheckpoint {
+    pub farm_plot: Pubkey,
+    pub digest: [u8; 32],
+    pub version: u8,                    // CHECKPOINT_VERSION the digest was computed with
+    pub checkpointed_at: i64,
+    pub bump: u8,
+}
+
+/// A secondary commodity grown on a multi-commodity (agroforestry) plot
+#[account]
+pub struct CropRecord {
+    pub farm_plot: Pubkey,
+    pub commodity_type: CommodityType,
+    pub area_hectares: f64,
+    pub harvested_weight_kg: u64,       // counted against this crop's yield capacity
+    pub bump: u8,
+}
+
+#[account]
+pub struct AccreditedCertifier {
+    pub certifier: Pubkey,
+    pub name: String,                   // max 64
+    pub accreditation_id: String,       // max 64
+    pub approved_by: Pubkey,
+    pub is_active: bool,
+    pub bump: u8,
+}
+
+#[account]
+pub struct Certification {
+    pub certifier: Pubkey,
+    pub farm_plot: Pubkey,
+    pub subject: Pubkey,                // farm plot or harvest batch being certified
+    pub cert_type: String,              // max 32, e.g. "organic", "fairtrade"
+    pub valid_from: i64,
+    pub valid_until: i64,
+    pub recorded_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct OracleSlash {
+    pub oracle: Pubkey,
+    pub verification: Pubkey,           // the overturned verification
+    pub contest: Pubkey,                // the contest the admin upheld
+    pub amount: u64,
+    pub slashed_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct CoordinateHistory {
+    pub farm_plot: Pubkey,
+    pub revision: u16,                  // revision these coordinates were in force for; 0 = as registered
+    pub coordinates: String,            // superseded polygon, max MAX_COORDINATES_LEN
+    pub bounding_box: Option<BoundingBox>,
+    pub reason: String,                 // max 64
+    pub changed_by: Pubkey,
+    pub changed_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct PendingConfigChange {
+    pub change: ConfigChange,
+    pub proposed_by: Pubkey,
+    pub proposed_at: i64,
+    pub effective_at: i64,              // apply_config_change is rejected before this
+    pub bump: u8,
+}
+
+#[account]
+pub struct VerificationIndex {
+    pub farm_plot: Pubkey,
+    pub segment: u32,                   // covers chain indices [segment * capacity, (segment + 1) * capacity)
+    pub entries: Vec<IndexedVerification>,  // max VERIFICATION_INDEX_CAPACITY
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct IndexedVerification {
+    pub verification: Pubkey,
+    pub verification_timestamp: i64,
+}
+
+#[account]
+pub struct InputMix {
+    pub processor: Pubkey,
+    pub mix_id: String,                 // max 32
+    pub commodity_type: CommodityType,
+    pub input_batches: Vec<Pubkey>,     // max MAX_MIX_INPUTS
+    pub input_weights_kg: Vec<u64>,     // parallel to input_batches
+    pub total_weight_kg: u64,
+    pub weighted_compliance_score: u8,  // input plots' scores weighted by input weight
+    pub tainted_input_count: u8,        // inputs non-compliant or below the commodity threshold
+    pub compliance_status: ComplianceStatus,    // PendingReview when any input is tainted
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct Payment {
+    pub harvest_batch: Pubkey,
+    pub payer: Pubkey,                  // batch owner at settlement
+    pub paid_to: Pubkey,
+    pub amount: u64,                    // in the currency's smallest unit
+    pub currency: String,               // max 8, e.g. "USD" or "USDC"
+    pub paid_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct Attestation {
+    pub farm_plot: Pubkey,
+    pub attestation_hash: [u8; 32],     // sha256 of the serialized PlotAttestation
+    pub attestation_version: u8,
+    pub attested_at: i64,
+    pub requested_by: Pubkey,
+    pub attestor: Pubkey,
+    pub signature: [u8; 64],            // attestor's Ed25519 signature over the PlotAttestation
+    pub bump: u8,
+}
+
+#[account]
+pub struct ApprovedAttestor {
+    pub attestor: Pubkey,
+    pub approved_by: Pubkey,
+    pub is_active: bool,
+    pub bump: u8,
+}
+
+/// Highest signed OracleMessage nonce accepted from an oracle, for replay protection
+#[account]
+pub struct OracleNonce {
+    pub oracle: Pubkey,
+    pub last_nonce: u64,
+    pub bump: u8,
+}
+
+impl OracleNonce {
+    /// Accepts `nonce` if it's above the last one seen (so nonces start at 1),
+    /// claiming the account on first use
+    pub fn consume(&mut self, oracle: Pubkey, bump: u8, nonce: u64) -> Result<()> {
+        if self.oracle == Pubkey::default() {
+            self.oracle = oracle;
+            self.bump = bump;
+        }
+        require!(nonce > self.last_nonce, ErrorCode::NonceReused);
+        self.last_nonce = nonce;
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Context Structures (with PDA seeds)
+// ============================================================================
+
+#[derive(Accounts)]
+#[instruction(
+    plot_id: String,
+    farmer_name: String,
+    location: String,
+    country_code: [u8; 2],
+    coordinates: String,
+    area_hectares: f64,
+    commodity_type: CommodityType
+)]
+pub struct RegisterFarmPlot<'info> {
+    #[account(
+        init_if_needed,
+        payer = farmer,
+        space = FARM_PLOT_SPACE,
+        seeds = [b"farm_plot", plot_id.as_bytes(), farmer.key().as_ref()],
+        bump
+    )]
+    pub farm_plot: Account<'info, FarmPlot>,
+    
+    #[account(
+        init_if_needed,
+        payer = farmer,
+        space = 8 + 64,
+        seeds = [b"farmer", farmer.key().as_ref()],
+        bump
+    )]
+    pub farmer_profile: Account<'info, FarmerProfile>,
+    
+    #[account(
+        init_if_needed,
+        payer = farmer,
+        space = AUDIT_LOG_SPACE,
+        seeds = [b"audit", farm_plot.key().as_ref()],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
+    /// Only required when registering with a GPS attestation
+    pub approved_device: Option<Account<'info, ApprovedDevice>>,
+    
+    /// CHECK: instructions sysvar, only required with a GPS attestation
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: policy PDA for the plot's commodity; may be uninitialized, in which
+    /// case the default policy applies
+    #[account(
+        seeds = [b"policy", &[commodity_type as u8]],
+        bump
+    )]
+    pub commodity_policy: UncheckedAccount<'info>,
+    
+    /// Required when the commodity policy demands a cosigner
+    pub cosigner: Option<Signer<'info>>,
+    
+    /// Registry entry for the cosigner
+    pub approved_cosigner: Option<Account<'info, ApprovedCosigner>>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    pub farmer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    batch_id: String,
+    weight: u64,
+    weight_unit: WeightUnit,
+    harvest_timestamp: i64,
+    head_count: Option<u32>,
+    ear_tag_ids: Vec<String>
+)]
+pub struct RegisterHarvestBatch<'info> {
+    #[account(
+        init,
+        payer = farmer,
+        space = 8 + 543 + ear_tag_ids.len() * (4 + MAX_EAR_TAG_LEN),
+        seeds = [b"harvest_batch", batch_id.as_bytes(), farmer.key().as_ref()],
+        bump
+    )]
+    pub harvest_batch: Account<'info, HarvestBatch>,
+    
+    /// CHECK: DDS record PDA at the new batch's address; must be empty, since a DDS
+    /// outlives its batch and would otherwise vouch for a reused batch_id
+    #[account(
+        seeds = [b"dds", harvest_batch.key().as_ref()],
+        bump
+    )]
+    pub prior_dds_record: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"farm_plot", farm_plot.plot_id.as_bytes(), farm_plot.farmer.as_ref()],
+        bump = farm_plot.bump
+    )]
+    pub farm_plot: Account<'info, FarmPlot>,
+    
+    #[account(
+        seeds = [b"farmer", farmer.key().as_ref()],
+        bump = farmer_profile.bump
+    )]
+    pub farmer_profile: Account<'info, FarmerProfile>,
+    
+    #[account(
+        init_if_needed,
+        payer = farmer,
+        space = AUDIT_LOG_SPACE,
+        seeds = [b"audit", farm_plot.key().as_ref()],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
+    /// Secondary crop the batch was harvested from; omit for the plot's own commodity
+    #[account(
+        mut,
+        seeds = [b"crop", farm_plot.key().as_ref(), &[crop.commodity_type as u8]],
+        bump = crop.bump
+    )]
+    pub crop: Option<Account<'info, CropRecord>>,
+    
+    /// CHECK: policy PDA for the batch's commodity; may be uninitialized, in which
+    /// case the default policy applies
+    #[account(
+        seeds = [
+            b"policy",
+            &[crop.as_ref().map_or(farm_plot.commodity_type, |crop| crop.commodity_type) as u8]
+        ],
+        bump
+    )]
+    pub commodity_policy: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    /// Admin co-signature to register a batch out of harvest order (historical backfill)
+    #[account(address = config.admin @ ErrorCode::Unauthorized)]
+    pub backfill_admin: Option<Signer<'info>>,
+    
+    #[account(mut)]
+    pub farmer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateBatchStatus<'info> {
+    #[account(
+        mut,
+        seeds = [b"harvest_batch", harvest_batch.batch_id.as_bytes(), authority.key().as_ref()],
+        bump = harvest_batch.bump
+    )]
+    pub harvest_batch: Account<'info, HarvestBatch>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 160,
+        seeds = [
+            b"batch_update",
+            harvest_batch.key().as_ref(),
+            &harvest_batch.update_seq.to_le_bytes()
+        ],
+        bump
+    )]
+    pub status_update: Account<'info, BatchStatusUpdate>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(verification_hash: String)]
+pub struct RecordSatelliteVerification<'info> {
+    // init_if_needed so a resubmission of the same imagery fails with
+    // VerificationAlreadyRecorded rather than a system program error
+    #[account(
+        init_if_needed,
+        payer = verifier,
+        space = SATELLITE_VERIFICATION_SPACE,
+        seeds = [
+            b"verification",
+            farm_plot.key().as_ref(),
+            verifier.key().as_ref(),
+            &imagery_seed(&verification_hash)
+        ],
+        bump
+    )]
+    pub verification: Account<'info, SatelliteVerification>,
+    
+    #[account(
+        mut,
+        seeds = [b"farm_plot", farm_plot.plot_id.as_bytes(), farm_plot.farmer.as_ref()],
+        bump = farm_plot.bump
+    )]
+    pub farm_plot: Account<'info, FarmPlot>,
+    
+    #[account(
+        mut,
+        seeds = [b"farmer", farm_plot.farmer.as_ref()],
+        bump = farmer_profile.bump
+    )]
+    pub farmer_profile: Account<'info, FarmerProfile>,
+    
+    #[account(
+        init_if_needed,
+        payer = verifier,
+        space = AUDIT_LOG_SPACE,
+        seeds = [b"audit", farm_plot.key().as_ref()],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
+    #[account(
+        init_if_needed,
+        payer = verifier,
+        space = VERIFICATION_INDEX_SPACE,
+        seeds = [
+            b"verification_index",
+            farm_plot.key().as_ref(),
+            &(farm_plot.verification_count / VERIFICATION_INDEX_CAPACITY).to_le_bytes()
+        ],
+        bump
+    )]
+    pub verification_index: Account<'info, VerificationIndex>,
+    
+    /// CHECK: policy PDA for the plot's commodity; may be uninitialized, in which
+    /// case the default policy applies
+    #[account(
+        seeds = [b"policy", &[farm_plot.commodity_type as u8]],
+        bump
+    )]
+    pub commodity_policy: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"challenge", farm_plot.key().as_ref(), verifier.key().as_ref()],
+        bump = challenge.bump
+    )]
+    pub challenge: Option<Account<'info, VerificationChallenge>>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    /// Verifier's oracle registration, required when the commodity policy sets a tier
+    #[account(
+        seeds = [b"oracle", verifier.key().as_ref()],
+        bump = registered_oracle.bump
+    )]
+    pub registered_oracle: Option<Account<'info, RegisteredOracle>>,
+    
+    #[account(
+        init_if_needed,
+        payer = verifier,
+        space = 8 + 48,
+        seeds = [b"oracle_nonce", verifier.key().as_ref()],
+        bump
+    )]
+    pub oracle_nonce: Account<'info, OracleNonce>,
+    
+    /// CHECK: instructions sysvar, read for the Ed25519 check of the oracle message
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    
+    /// Cooperative account funding the verification, if not the verifier
+    #[account(mut)]
+    pub funding_pool: Option<Signer<'info>>,
+    
+    #[account(mut)]
+    pub verifier: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RecordProcessing<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 120,
+        seeds = [
+            b"processing",
+            harvest_batch.key().as_ref(),
+            &harvest_batch.processing_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub processing_record: Account<'info, ProcessingRecord>,
+    
+    #[account(
+        mut,
+        seeds = [b"harvest_batch", harvest_batch.batch_id.as_bytes(), authority.key().as_ref()],
+        bump = harvest_batch.bump
+    )]
+    pub harvest_batch: Account<'info, HarvestBatch>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GenerateDDSData<'info> {
+    #[account(
+        seeds = [b"harvest_batch", harvest_batch.batch_id.as_bytes(), harvest_batch.farmer.as_ref()],
+        bump = harvest_batch.bump
+    )]
+    pub harvest_batch: Account<'info, HarvestBatch>,
+    
+    #[account(
+        seeds = [b"farm_plot", farm_plot.plot_id.as_bytes(), farm_plot.farmer.as_ref()],
+        bump = farm_plot.bump
+    )]
+    pub farm_plot: Account<'info, FarmPlot>,
+    
+    /// Latest processing step, which the DDS reports
+    #[account(
+        seeds = [
+            b"processing",
+            harvest_batch.key().as_ref(),
+            &processing_record.step.to_le_bytes()
+        ],
+        bump = processing_record.bump,
+        constraint = processing_record.step + 1 == harvest_batch.processing_count @ ErrorCode::ProcessingStepNotLatest
+    )]
+    pub processing_record: Option<Account<'info, ProcessingRecord>>,
+    
+    /// In-season verification, required when the plot has a harvest season
+    #[account(
+        seeds = [
+            b"verification",
+            farm_plot.key().as_ref(),
+            verification.verifier.as_ref(),
+            &imagery_seed(&verification.verification_hash)
+        ],
+        bump = verification.bump
+    )]
+    pub verification: Option<Account<'info, SatelliteVerification>>,
+    
+    #[account(
+        seeds = [b"payment", harvest_batch.key().as_ref()],
+        bump = payment.bump
+    )]
+    pub payment: Option<Account<'info, Payment>>,
+}
+
+#[derive(Accounts)]
+#[instruction(lot_id: String)]
+pub struct CreateStorageLot<'info> {
+    #[account(
+        init,
+        payer = operator,
+        space = 8 + 100,
+        seeds = [b"storage_lot", lot_id.as_bytes(), operator.key().as_ref()],
+        bump
+    )]
+    pub storage_lot: Account<'info, StorageLot>,
+    
+    #[account(mut)]
+    pub operator: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositToLot<'info> {
+    #[account(
+        mut,
+        seeds = [b"storage_lot", storage_lot.lot_id.as_bytes(), storage_lot.operator.as_ref()],
+        bump = storage_lot.bump
+    )]
+    pub storage_lot: Account<'info, StorageLot>,
+    
+    // One deposit record per batch and lot; depositing also consumes the batch
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 100,
+        seeds = [b"lot_deposit", storage_lot.key().as_ref(), harvest_batch.key().as_ref()],
+        bump
+    )]
+    pub lot_deposit: Account<'info, LotDeposit>,
+    
+    #[account(
+        mut,
+        seeds = [b"harvest_batch", harvest_batch.batch_id.as_bytes(), authority.key().as_ref()],
+        bump = harvest_batch.bump
+    )]
+    pub harvest_batch: Account<'info, HarvestBatch>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFromLot<'info> {
+    #[account(
+        mut,
+        seeds = [b"storage_lot", storage_lot.lot_id.as_bytes(), operator.key().as_ref()],
+        bump = storage_lot.bump
+    )]
+    pub storage_lot: Account<'info, StorageLot>,
+    
+    #[account(
+        init,
+        payer = operator,
+        space = 8 + 160,
+        seeds = [
+            b"lot_withdrawal",
+            storage_lot.key().as_ref(),
+            &storage_lot.withdrawal_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub withdrawal: Account<'info, LotWithdrawal>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    pub operator: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(verifier: Pubkey)]
+pub struct IssueVerificationChallenge<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + 120,
+        seeds = [b"challenge", farm_plot.key().as_ref(), verifier.as_ref()],
+        bump
+    )]
+    pub challenge: Account<'info, VerificationChallenge>,
+    
+    #[account(
+        seeds = [b"farm_plot", farm_plot.plot_id.as_bytes(), farm_plot.farmer.as_ref()],
+        bump = farm_plot.bump
+    )]
+    pub farm_plot: Account<'info, FarmPlot>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    /// The plot's farmer or the config admin
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RecordLandCoverBaseline<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 150,
+        seeds = [b"baseline", farm_plot.key().as_ref()],
+        bump
+    )]
+    pub baseline: Account<'info, LandCoverBaseline>,
+    
+    #[account(
+        mut,
+        seeds = [b"farm_plot", farm_plot.plot_id.as_bytes(), farm_plot.farmer.as_ref()],
+        bump = farm_plot.bump
+    )]
+    pub farm_plot: Account<'info, FarmPlot>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AUDIT_LOG_SPACE,
+        seeds = [b"audit", farm_plot.key().as_ref()],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
+    /// Authority's oracle registration; without one the authority must be the farmer
+    #[account(
+        seeds = [b"oracle", authority.key().as_ref()],
+        bump = registered_oracle.bump
+    )]
+    pub registered_oracle: Option<Account<'info, RegisteredOracle>>,
+    
+    /// The plot's farmer or a registered oracle
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 256,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(commodity_type: CommodityType)]
+pub struct SetCommodityPolicy<'info> {
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = COMMODITY_POLICY_SPACE,
+        seeds = [b"policy", &[commodity_type as u8]],
+        bump
+    )]
+    pub commodity_policy: Account<'info, CommodityPolicy>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AssignDdsReference<'info> {
+    #[account(
+        mut,
+        seeds = [b"harvest_batch", harvest_batch.batch_id.as_bytes(), authority.key().as_ref()],
+        bump = harvest_batch.bump
+    )]
+    pub harvest_batch: Account<'info, HarvestBatch>,
+    
+    #[account(
+        seeds = [b"dds", harvest_batch.key().as_ref()],
+        bump = dds_record.bump,
+        has_one = harvest_batch @ ErrorCode::DDSNotRecorded
+    )]
+    pub dds_record: Account<'info, DDSRecord>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(device: Pubkey)]
+pub struct ApproveGpsDevice<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 80,
+        seeds = [b"device", device.as_ref()],
+        bump
+    )]
+    pub approved_device: Account<'info, ApprovedDevice>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeGpsDevice<'info> {
+    #[account(
+        mut,
+        seeds = [b"device", approved_device.device.as_ref()],
+        bump = approved_device.bump
+    )]
+    pub approved_device: Account<'info, ApprovedDevice>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(cosigner: Pubkey)]
+pub struct ApproveCosigner<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 80,
+        seeds = [b"cosigner", cosigner.as_ref()],
+        bump
+    )]
+    pub approved_cosigner: Account<'info, ApprovedCosigner>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeCosigner<'info> {
+    #[account(
+        mut,
+        seeds = [b"cosigner", approved_cosigner.cosigner.as_ref()],
+        bump = approved_cosigner.bump
+    )]
+    pub approved_cosigner: Account<'info, ApprovedCosigner>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(oracle: Pubkey)]
+pub struct ApproveOracle<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 80,
+        seeds = [b"oracle", oracle.as_ref()],
+        bump
+    )]
+    pub registered_oracle: Account<'info, RegisteredOracle>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeOracle<'info> {
+    #[account(
+        mut,
+        seeds = [b"oracle", registered_oracle.oracle.as_ref()],
+        bump = registered_oracle.bump
+    )]
+    pub registered_oracle: Account<'info, RegisteredOracle>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RecordDDS<'info> {
+    #[account(
+        init,
+        payer = farmer,
+        space = 8 + 150,
+        seeds = [b"dds", harvest_batch.key().as_ref()],
+        bump
+    )]
+    pub dds_record: Account<'info, DDSRecord>,
+    
+    #[account(
+        seeds = [b"harvest_batch", harvest_batch.batch_id.as_bytes(), farmer.key().as_ref()],
+        bump = harvest_batch.bump
+    )]
+    pub harvest_batch: Account<'info, HarvestBatch>,
+    
+    #[account(
+        address = harvest_batch.farm_plot,
+        seeds = [b"farm_plot", farm_plot.plot_id.as_bytes(), farm_plot.farmer.as_ref()],
+        bump = farm_plot.bump
+    )]
+    pub farm_plot: Account<'info, FarmPlot>,
+    
+    /// Latest processing step, which the DDS reports
+    #[account(
+        seeds = [
+            b"processing",
+            harvest_batch.key().as_ref(),
+            &processing_record.step.to_le_bytes()
+        ],
+        bump = processing_record.bump,
+        constraint = processing_record.step + 1 == harvest_batch.processing_count @ ErrorCode::ProcessingStepNotLatest
+    )]
+    pub processing_record: Option<Account<'info, ProcessingRecord>>,
+    
+    /// In-season verification, required when the plot has a harvest season
+    #[account(
+        seeds = [
+            b"verification",
+            farm_plot.key().as_ref(),
+            verification.verifier.as_ref(),
+            &imagery_seed(&verification.verification_hash)
+        ],
+        bump = verification.bump
+    )]
+    pub verification: Option<Account<'info, SatelliteVerification>>,
+    
+    #[account(
+        seeds = [b"payment", harvest_batch.key().as_ref()],
+        bump = payment.bump
+    )]
+    pub payment: Option<Account<'info, Payment>>,
+    
+    #[account(mut)]
+    pub farmer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseBatch<'info> {
+    #[account(
+        mut,
+        close = farmer,
+        seeds = [b"harvest_batch", harvest_batch.batch_id.as_bytes(), farmer.key().as_ref()],
+        bump = harvest_batch.bump,
+        has_one = farmer @ ErrorCode::Unauthorized
+    )]
+    pub harvest_batch: Account<'info, HarvestBatch>,
+    
+    /// DDS filed for this batch; required unless the batch was consumed into a lot
+    #[account(
+        seeds = [b"dds", harvest_batch.key().as_ref()],
+        bump = dds_record.bump,
+        has_one = harvest_batch @ ErrorCode::DDSRequiredBeforeClose
+    )]
+    pub dds_record: Option<Account<'info, DDSRecord>>,
+    
+    #[account(mut)]
+    pub farmer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(batch_id: String, commodity_type: CommodityType)]
+pub struct RegisterMultiplotBatch<'info> {
+    #[account(
+        init,
+        payer = farmer,
+        space = 8 + 743,
+        seeds = [b"harvest_batch", batch_id.as_bytes(), farmer.key().as_ref()],
+        bump
+    )]
+    pub harvest_batch: Account<'info, HarvestBatch>,
+    
+    /// CHECK: DDS record PDA at the new batch's address; must be empty, since a DDS
+    /// outlives its batch and would otherwise vouch for a reused batch_id
+    #[account(
+        seeds = [b"dds", harvest_batch.key().as_ref()],
+        bump
+    )]
+    pub prior_dds_record: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"farmer", farmer.key().as_ref()],
+        bump = farmer_profile.bump
+    )]
+    pub farmer_profile: Account<'info, FarmerProfile>,
+    
+    /// CHECK: policy PDA for the batch's commodity; may be uninitialized, in which
+    /// case the default policy applies
+    #[account(
+        seeds = [b"policy", &[commodity_type as u8]],
+        bump
+    )]
+    pub commodity_policy: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    pub farmer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RecallBatch<'info> {
+    #[account(
+        mut,
+        seeds = [b"harvest_batch", harvest_batch.batch_id.as_bytes(), harvest_batch.farmer.as_ref()],
+        bump = harvest_batch.bump
+    )]
+    pub harvest_batch: Account<'info, HarvestBatch>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetComplianceSnapshot<'info> {
+    #[account(
+        seeds = [b"farm_plot", farm_plot.plot_id.as_bytes(), farm_plot.farmer.as_ref()],
+        bump = farm_plot.bump
+    )]
+    pub farm_plot: Account<'info, FarmPlot>,
+    
+    /// CHECK: policy PDA for the plot's commodity; may be uninitialized, in which
+    /// case the default policy applies
+    #[account(
+        seeds = [b"policy", &[farm_plot.commodity_type as u8]],
+        bump
+    )]
+    pub commodity_policy: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(attributes: Vec<BatchAttribute>)]
+pub struct SetBatchAttributes<'info> {
+    // Resize the account by the difference between the old and new attribute lists
+    #[account(
+        mut,
+        seeds = [b"harvest_batch", harvest_batch.batch_id.as_bytes(), farmer.key().as_ref()],
+        bump = harvest_batch.bump,
+        realloc = harvest_batch.to_account_info().data_len()
+            - attributes_space(&harvest_batch.sustainability_attrs)
+            + attributes_space(&attributes),
+        realloc::payer = farmer,
+        realloc::zero = false
+    )]
+    pub harvest_batch: Account<'info, HarvestBatch>,
+    
+    #[account(mut)]
+    pub farmer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyMetadataHash<'info> {
+    #[account(
+        seeds = [b"farm_plot", farm_plot.plot_id.as_bytes(), farm_plot.farmer.as_ref()],
+        bump = farm_plot.bump
+    )]
+    pub farm_plot: Account<'info, FarmPlot>,
+}
+
+#[derive(Accounts)]
+pub struct MarkExpired<'info> {
+    #[account(
+        mut,
+        seeds = [b"farm_plot", farm_plot.plot_id.as_bytes(), farm_plot.farmer.as_ref()],
+        bump = farm_plot.bump
+    )]
+    pub farm_plot: Account<'info, FarmPlot>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = AUDIT_LOG_SPACE,
+        seeds = [b"audit", farm_plot.key().as_ref()],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
+    #[account(
+        mut,
+        seeds = [b"farmer", farm_plot.farmer.as_ref()],
+        bump = farmer_profile.bump
+    )]
+    pub farmer_profile: Account<'info, FarmerProfile>,
+    
+    /// Whoever cranks the expiry; funds the audit log if the plot has none yet
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterPlotsBatch<'info> {
+    #[account(
+        init_if_needed,
+        payer = farmer,
+        space = 8 + 64,
+        seeds = [b"farmer", farmer.key().as_ref()],
+        bump
+    )]
+    pub farmer_profile: Account<'info, FarmerProfile>,
+    
+    /// Required when any plot's commodity policy demands a cosigner
+    pub cosigner: Option<Signer<'info>>,
+    
+    /// Registry entry for the cosigner
+    pub approved_cosigner: Option<Account<'info, ApprovedCosigner>>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    pub farmer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetBatchProvenance<'info> {
+    #[account(
+        seeds = [b"harvest_batch", harvest_batch.batch_id.as_bytes(), harvest_batch.farmer.as_ref()],
+        bump = harvest_batch.bump
+    )]
+    pub harvest_batch: Account<'info, HarvestBatch>,
+    
+    #[account(
+        address = harvest_batch.farm_plot,
+        seeds = [b"farm_plot", farm_plot.plot_id.as_bytes(), farm_plot.farmer.as_ref()],
+        bump = farm_plot.bump
+    )]
+    pub farm_plot: Account<'info, FarmPlot>,
+}
+
+#[derive(Accounts)]
+pub struct CheckBatchEligibility<'info> {
+    #[account(
+        seeds = [b"harvest_batch", harvest_batch.batch_id.as_bytes(), harvest_batch.farmer.as_ref()],
+        bump = harvest_batch.bump
+    )]
+    pub harvest_batch: Account<'info, HarvestBatch>,
+    
+    #[account(
+        address = harvest_batch.farm_plot,
+        seeds = [b"farm_plot", farm_plot.plot_id.as_bytes(), farm_plot.farmer.as_ref()],
+        bump = farm_plot.bump
+    )]
+    pub farm_plot: Account<'info, FarmPlot>,
+    
+    /// CHECK: policy PDA for the plot's commodity; may be uninitialized, in which
+    /// case the default policy applies
+    #[account(
+        seeds = [b"policy", &[farm_plot.commodity_type as u8]],
+        bump
+    )]
+    pub commodity_policy: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(system: String, id: String)]
+pub struct AddExternalId<'info> {
+    #[account(
+        mut,
+        seeds = [b"harvest_batch", harvest_batch.batch_id.as_bytes(), farmer.key().as_ref()],
+        bump = harvest_batch.bump,
+        realloc = harvest_batch.to_account_info().data_len() + external_id_space(&system, &id),
+        realloc::payer = farmer,
+        realloc::zero = false
+    )]
+    pub harvest_batch: Account<'info, HarvestBatch>,
+    
+    #[account(mut)]
+    pub farmer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPlotActive<'info> {
+    #[account(
+        mut,
+        seeds = [b"farm_plot", farm_plot.plot_id.as_bytes(), farm_plot.farmer.as_ref()],
+        bump = farm_plot.bump
+    )]
+    pub farm_plot: Account<'info, FarmPlot>,
+    
+    #[account(
+        mut,
+        seeds = [b"farmer", farmer.key().as_ref()],
+        bump = farmer_profile.bump
+    )]
+    pub farmer_profile: Account<'info, FarmerProfile>,
+    
+    #[account(
+        init_if_needed,
+        payer = farmer,
+        space = AUDIT_LOG_SPACE,
+        seeds = [b"audit", farm_plot.key().as_ref()],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
+    #[account(mut)]
+    pub farmer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateDerivative<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 140,
+        seeds = [
+            b"derivative",
+            harvest_batch.key().as_ref(),
+            &harvest_batch.derivative_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub derivative: Account<'info, DerivativeProduct>,
+    
+    #[account(
+        mut,
+        seeds = [b"harvest_batch", harvest_batch.batch_id.as_bytes(), authority.key().as_ref()],
+        bump = harvest_batch.bump
+    )]
+    pub harvest_batch: Account<'info, HarvestBatch>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RecordSatelliteVerificationBatch<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    /// Verifier's oracle registration, required when the commodity policy sets a tier
+    #[account(
+        seeds = [b"oracle", verifier.key().as_ref()],
+        bump = registered_oracle.bump
+    )]
+    pub registered_oracle: Option<Account<'info, RegisteredOracle>>,
+    
+    #[account(
+        init_if_needed,
+        payer = verifier,
+        space = 8 + 48,
+        seeds = [b"oracle_nonce", verifier.key().as_ref()],
+        bump
+    )]
+    pub oracle_nonce: Account<'info, OracleNonce>,
+    
+    /// CHECK: instructions sysvar, read for the Ed25519 checks of the oracle messages
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    
+    /// Cooperative account funding the verifications, if not the verifier
+    #[account(mut)]
+    pub funding_pool: Option<Signer<'info>>,
+    
+    #[account(mut)]
+    pub verifier: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CheckpointPlot<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + 80,
+        seeds = [b"checkpoint", farm_plot.key().as_ref()],
+        bump
+    )]
+    pub checkpoint: Account<'info, PlotCheckpoint>,
+    
+    #[account(
+        seeds = [b"farm_plot", farm_plot.plot_id.as_bytes(), farm_plot.farmer.as_ref()],
+        bump = farm_plot.bump
+    )]
+    pub farm_plot: Account<'info, FarmPlot>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(commodity_type: CommodityType)]
+pub struct AddCrop<'info> {
+    #[account(
+        init,
+        payer = farmer,
+        space = 8 + 64,
+        seeds = [b"crop", farm_plot.key().as_ref(), &[commodity_type as u8]],
+        bump
+    )]
+    pub crop: Account<'info, CropRecord>,
+    
+    #[account(
+        mut,
+        seeds = [b"farm_plot", farm_plot.plot_id.as_bytes(), farm_plot.farmer.as_ref()],
+        bump = farm_plot.bump
+    )]
+    pub farm_plot: Account<'info, FarmPlot>,
+    
+    #[account(mut)]
+    pub farmer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(verification_hash: String)]
+pub struct ContestVerification<'info> {
+    // init_if_needed so a resubmission of the same imagery fails with
+    // VerificationAlreadyRecorded rather than a system program error
+    #[account(
+        init_if_needed,
+        payer = contester,
+        space = SATELLITE_VERIFICATION_SPACE,
+        seeds = [
+            b"verification",
+            farm_plot.key().as_ref(),
+            contester.key().as_ref(),
+            &imagery_seed(&verification_hash)
+        ],
+        bump
+    )]
+    pub contest: Account<'info, SatelliteVerification>,
+    
+    #[account(
+        seeds = [
+            b"verification",
+            farm_plot.key().as_ref(),
+            original.verifier.as_ref(),
+            &imagery_seed(&original.verification_hash)
+        ],
+        bump = original.bump
+    )]
+    pub original: Account<'info, SatelliteVerification>,
+    
+    #[account(
+        mut,
+        seeds = [b"farm_plot", farm_plot.plot_id.as_bytes(), farm_plot.farmer.as_ref()],
+        bump = farm_plot.bump
+    )]
+    pub farm_plot: Account<'info, FarmPlot>,
+    
+    #[account(
+        mut,
+        seeds = [b"farmer", farm_plot.farmer.as_ref()],
+        bump = farmer_profile.bump
+    )]
+    pub farmer_profile: Account<'info, FarmerProfile>,
+    
+    #[account(
+        init_if_needed,
+        payer = contester,
+        space = AUDIT_LOG_SPACE,
+        seeds = [b"audit", farm_plot.key().as_ref()],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
+    #[account(
+        init_if_needed,
+        payer = contester,
+        space = VERIFICATION_INDEX_SPACE,
+        seeds = [
+            b"verification_index",
+            farm_plot.key().as_ref(),
+            &(farm_plot.verification_count / VERIFICATION_INDEX_CAPACITY).to_le_bytes()
+        ],
+        bump
+    )]
+    pub verification_index: Account<'info, VerificationIndex>,
+    
+    #[account(
+        seeds = [b"oracle", contester.key().as_ref()],
+        bump = registered_oracle.bump
+    )]
+    pub registered_oracle: Account<'info, RegisteredOracle>,
+    
+    #[account(
+        init_if_needed,
+        payer = contester,
+        space = 8 + 48,
+        seeds = [b"oracle_nonce", contester.key().as_ref()],
+        bump
+    )]
+    pub oracle_nonce: Account<'info, OracleNonce>,
+    
+    /// CHECK: instructions sysvar, read for the Ed25519 check of the oracle message
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    
+    /// CHECK: policy PDA for the plot's commodity; may be uninitialized, in which
+    /// case the default policy applies
+    #[account(
+        seeds = [b"policy", &[farm_plot.commodity_type as u8]],
+        bump
+    )]
+    pub commodity_policy: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    pub contester: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MarkBatchExpired<'info> {
+    #[account(
+        mut,
+        seeds = [b"harvest_batch", harvest_batch.batch_id.as_bytes(), harvest_batch.farmer.as_ref()],
+        bump = harvest_batch.bump
+    )]
+    pub harvest_batch: Account<'info, HarvestBatch>,
+}
+
+#[derive(Accounts)]
+#[instruction(certifier: Pubkey)]
+pub struct ApproveCertifier<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 210,
+        seeds = [b"certifier", certifier.as_ref()],
+        bump
+    )]
+    pub accredited_certifier: Account<'info, AccreditedCertifier>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeCertifier<'info> {
+    #[account(
+        mut,
+        seeds = [b"certifier", accredited_certifier.certifier.as_ref()],
+        bump = accredited_certifier.bump
+    )]
+    pub accredited_certifier: Account<'info, AccreditedCertifier>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(subject: Pubkey, cert_type: String)]
+pub struct RecordCertification<'info> {
+    #[account(
+        init,
+        payer = certifier,
+        space = 8 + 160,
+        seeds = [b"certification", subject.as_ref(), certifier.key().as_ref(), cert_type.as_bytes()],
+        bump
+    )]
+    pub certification: Account<'info, Certification>,
+    
+    #[account(
+        seeds = [b"certifier", certifier.key().as_ref()],
+        bump = accredited_certifier.bump,
+        constraint = accredited_certifier.is_active @ ErrorCode::CertifierNotAccredited
+    )]
+    pub accredited_certifier: Account<'info, AccreditedCertifier>,
+    
+    #[account(
+        seeds = [b"farm_plot", farm_plot.plot_id.as_bytes(), farm_plot.farmer.as_ref()],
+        bump = farm_plot.bump
+    )]
+    pub farm_plot: Account<'info, FarmPlot>,
+    
+    #[account(
+        seeds = [b"harvest_batch", harvest_batch.batch_id.as_bytes(), harvest_batch.farmer.as_ref()],
+        bump = harvest_batch.bump
+    )]
+    pub harvest_batch: Option<Account<'info, HarvestBatch>>,
+    
+    #[account(mut)]
+    pub certifier: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterOracleWithStake<'info> {
+    #[account(
+        init,
+        payer = oracle,
+        space = 8 + 80,
+        seeds = [b"oracle", oracle.key().as_ref()],
+        bump
+    )]
+    pub registered_oracle: Account<'info, RegisteredOracle>,
+    
+    #[account(
+        mut,
+        seeds = [b"oracle_stake", oracle.key().as_ref()],
+        bump
+    )]
+    pub stake_vault: SystemAccount<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    pub admin: Signer<'info>,
+    
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawOracleStake<'info> {
+    #[account(
+        mut,
+        seeds = [b"oracle", oracle.key().as_ref()],
+        bump = registered_oracle.bump
+    )]
+    pub registered_oracle: Account<'info, RegisteredOracle>,
+    
+    #[account(
+        mut,
+        seeds = [b"oracle_stake", oracle.key().as_ref()],
+        bump
+    )]
+    pub stake_vault: SystemAccount<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SlashOracle<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 120,
+        seeds = [b"slash", verification.key().as_ref()],
+        bump
+    )]
+    pub oracle_slash: Account<'info, OracleSlash>,
+    
+    #[account(
+        mut,
+        seeds = [b"oracle", registered_oracle.oracle.as_ref()],
+        bump = registered_oracle.bump
+    )]
+    pub registered_oracle: Account<'info, RegisteredOracle>,
+    
+    #[account(
+        mut,
+        seeds = [b"oracle_stake", registered_oracle.oracle.as_ref()],
+        bump
+    )]
+    pub stake_vault: SystemAccount<'info>,
+    
+    pub verification: Account<'info, SatelliteVerification>,
+    
+    pub contest: Account<'info, SatelliteVerification>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetQrPayload<'info> {
+    #[account(
+        seeds = [b"harvest_batch", harvest_batch.batch_id.as_bytes(), harvest_batch.farmer.as_ref()],
+        bump = harvest_batch.bump
+    )]
+    pub harvest_batch: Account<'info, HarvestBatch>,
+    
+    #[account(
+        address = harvest_batch.farm_plot,
+        seeds = [b"farm_plot", farm_plot.plot_id.as_bytes(), farm_plot.farmer.as_ref()],
+        bump = farm_plot.bump
+    )]
+    pub farm_plot: Account<'info, FarmPlot>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePlotCoordinates<'info> {
+    #[account(
+        mut,
+        seeds = [b"farm_plot", farm_plot.plot_id.as_bytes(), farm_plot.farmer.as_ref()],
+        bump = farm_plot.bump
+    )]
+    pub farm_plot: Account<'info, FarmPlot>,
+    
+    #[account(
+        init,
+        payer = farmer,
+        space = COORDINATE_HISTORY_SPACE,
+        seeds = [
+            b"coord_history",
+            farm_plot.key().as_ref(),
+            &farm_plot.coordinate_revision.to_le_bytes()
+        ],
+        bump
+    )]
+    pub coordinate_history: Account<'info, CoordinateHistory>,
+    
+    #[account(
+        init_if_needed,
+        payer = farmer,
+        space = AUDIT_LOG_SPACE,
+        seeds = [b"audit", farm_plot.key().as_ref()],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    pub farmer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetCooperativeStats {}
+
+#[derive(Accounts)]
+pub struct FinalizeForExport<'info> {
+    #[account(
+        mut,
+        seeds = [b"harvest_batch", harvest_batch.batch_id.as_bytes(), farmer.key().as_ref()],
+        bump = harvest_batch.bump
+    )]
+    pub harvest_batch: Account<'info, HarvestBatch>,
+    
+    pub farmer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeConfigChange<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 128,
+        seeds = [b"pending_config"],
+        bump
+    )]
+    pub pending_change: Account<'info, PendingConfigChange>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApplyConfigChange<'info> {
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"pending_config"],
+        bump = pending_change.bump
+    )]
+    pub pending_change: Account<'info, PendingConfigChange>,
+    
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"policy", &[commodity_policy.commodity_type as u8]],
+        bump = commodity_policy.bump
+    )]
+    pub commodity_policy: Option<Account<'info, CommodityPolicy>>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelConfigChange<'info> {
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"pending_config"],
+        bump = pending_change.bump
+    )]
+    pub pending_change: Account<'info, PendingConfigChange>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TransferBatchOwnership<'info> {
+    #[account(
+        mut,
+        seeds = [b"harvest_batch", harvest_batch.batch_id.as_bytes(), harvest_batch.farmer.as_ref()],
+        bump = harvest_batch.bump
+    )]
+    pub harvest_batch: Account<'info, HarvestBatch>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetVerificationsInRange<'info> {
+    #[account(
+        seeds = [b"farm_plot", farm_plot.plot_id.as_bytes(), farm_plot.farmer.as_ref()],
+        bump = farm_plot.bump
+    )]
+    pub farm_plot: Account<'info, FarmPlot>,
+}
+
+#[derive(Accounts)]
+pub struct SetFarmerSuspension<'info> {
+    #[account(
+        mut,
+        seeds = [b"farmer", farmer_profile.farmer.as_ref()],
+        bump = farmer_profile.bump
+    )]
+    pub farmer_profile: Account<'info, FarmerProfile>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(mix_id: String, commodity_type: CommodityType)]
+pub struct RecordInputMix<'info> {
+    #[account(
+        init,
+        payer = processor,
+        space = 8 + 310,
+        seeds = [b"input_mix", processor.key().as_ref(), mix_id.as_bytes()],
+        bump
+    )]
+    pub input_mix: Account<'info, InputMix>,
+    
+    /// CHECK: policy PDA for the mix's commodity; may be uninitialized, in which
+    /// case the default policy applies
+    #[account(
+        seeds = [b"policy", &[commodity_type as u8]],
+        bump
+    )]
+    pub commodity_policy: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub processor: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RecordPayment<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 128,
+        seeds = [b"payment", harvest_batch.key().as_ref()],
+        bump
+    )]
+    pub payment: Account<'info, Payment>,
+    
+    #[account(
+        seeds = [b"harvest_batch", harvest_batch.batch_id.as_bytes(), harvest_batch.farmer.as_ref()],
+        bump = harvest_batch.bump
+    )]
+    pub harvest_batch: Account<'info, HarvestBatch>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResetSeason<'info> {
+    #[account(
+        mut,
+        seeds = [b"farm_plot", farm_plot.plot_id.as_bytes(), farm_plot.farmer.as_ref()],
+        bump = farm_plot.bump
+    )]
+    pub farm_plot: Account<'info, FarmPlot>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(attested_at: i64)]
+pub struct SignAttestation<'info> {
+    #[account(
+        init,
+        payer = requester,
+        space = 8 + 216,
+        seeds = [b"attestation", farm_plot.key().as_ref(), &attested_at.to_le_bytes()],
+        bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+    
+    #[account(
+        seeds = [b"farm_plot", farm_plot.plot_id.as_bytes(), farm_plot.farmer.as_ref()],
+        bump = farm_plot.bump
+    )]
     pub farm_plot: Account<'info, FarmPlot>,
     
+    #[account(
+        seeds = [b"attestor", approved_attestor.attestor.as_ref()],
+        bump = approved_attestor.bump
+    )]
+    pub approved_attestor: Account<'info, ApprovedAttestor>,
+    
+    /// CHECK: instructions sysvar, read for the Ed25519 check of the attestor's signature
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    
     #[account(mut)]
-    pub farmer: Signer<'info>,
+    pub requester: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(attestor: Pubkey)]
+pub struct ApproveAttestor<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 80,
+        seeds = [b"attestor", attestor.as_ref()],
+        bump
+    )]
+    pub approved_attestor: Account<'info, ApprovedAttestor>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeAttestor<'info> {
+    #[account(
+        mut,
+        seeds = [b"attestor", approved_attestor.attestor.as_ref()],
+        bump = approved_attestor.bump
+    )]
+    pub approved_attestor: Account<'info, ApprovedAttestor>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RedactFarmerPii<'info> {
+    #[account(
+        mut,
+        seeds = [b"farm_plot", farm_plot.plot_id.as_bytes(), farm_plot.farmer.as_ref()],
+        bump = farm_plot.bump
+    )]
+    pub farm_plot: Account<'info, FarmPlot>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AUDIT_LOG_SPACE,
+        seeds = [b"audit", farm_plot.key().as_ref()],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
     
     pub system_program: Program<'info, System>,
 }
 
-#[derive(Accounts)]
-pub struct UpdateBatchStatus<'info> {
-    #[account(
-        mut,
-        seeds = [b"harvest_batch", harvest_batch.batch_id.as_bytes(), authority.key().as_ref()],
-        bump = harvest_batch.bump
-    )]
-    pub harvest_batch: Account<'info, HarvestBatch>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
+#[derive(Accounts)]
+pub struct GetRouteHistory<'info> {
+    #[account(
+        seeds = [b"harvest_batch", harvest_batch.batch_id.as_bytes(), harvest_batch.farmer.as_ref()],
+        bump = harvest_batch.bump
+    )]
+    pub harvest_batch: Account<'info, HarvestBatch>,
+}
+
+#[derive(Accounts)]
+pub struct CommitDocuments<'info> {
+    #[account(
+        mut,
+        seeds = [b"harvest_batch", harvest_batch.batch_id.as_bytes(), harvest_batch.farmer.as_ref()],
+        bump = harvest_batch.bump
+    )]
+    pub harvest_batch: Account<'info, HarvestBatch>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyDocumentInclusion<'info> {
+    #[account(
+        seeds = [b"harvest_batch", harvest_batch.batch_id.as_bytes(), harvest_batch.farmer.as_ref()],
+        bump = harvest_batch.bump
+    )]
+    pub harvest_batch: Account<'info, HarvestBatch>,
+}
+
+#[derive(Accounts)]
+pub struct IsEuExportable<'info> {
+    #[account(
+        seeds = [b"farm_plot", farm_plot.plot_id.as_bytes(), farm_plot.farmer.as_ref()],
+        bump = farm_plot.bump
+    )]
+    pub farm_plot: Account<'info, FarmPlot>,
+    
+    /// CHECK: policy PDA for the plot's commodity; may be uninitialized, in which
+    /// case the default policy applies
+    #[account(
+        seeds = [b"policy", &[farm_plot.commodity_type as u8]],
+        bump
+    )]
+    pub commodity_policy: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"harvest_batch", harvest_batch.batch_id.as_bytes(), harvest_batch.farmer.as_ref()],
+        bump = harvest_batch.bump
+    )]
+    pub harvest_batch: Option<Account<'info, HarvestBatch>>,
+}
+
+// ============================================================================
+// Enums
+// ============================================================================
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum CommodityType {
+    Cocoa,
+    Coffee,
+    PalmOil,
+    Soy,
+    Cattle,
+    Rubber,
+    Timber,
+    Other,
+}
+
+impl CommodityType {
+    /// Re-verification interval used when the commodity's policy doesn't set one:
+    /// land cleared for annual crops and pasture changes fastest, forestry slowest
+    pub fn default_validity_seconds(self) -> i64 {
+        match self {
+            CommodityType::Soy | CommodityType::Cattle => 90 * SECONDS_PER_DAY,
+            CommodityType::Cocoa | CommodityType::Coffee | CommodityType::PalmOil => 180 * SECONDS_PER_DAY,
+            CommodityType::Rubber | CommodityType::Other => DEFAULT_VERIFICATION_VALIDITY_SECONDS,
+            CommodityType::Timber => 2 * DEFAULT_VERIFICATION_VALIDITY_SECONDS,
+        }
+    }
+    
+    /// Display name used on printed labels
+    pub fn name(self) -> &'static str {
+        match self {
+            CommodityType::Cocoa => "Cocoa",
+            CommodityType::Coffee => "Coffee",
+            CommodityType::PalmOil => "Palm Oil",
+            CommodityType::Soy => "Soy",
+            CommodityType::Cattle => "Cattle",
+            CommodityType::Rubber => "Rubber",
+            CommodityType::Timber => "Timber",
+            CommodityType::Other => "Other",
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DeforestationRisk {
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum BatchStatus {
+    Harvested,
+    Processing,
+    InTransit,
+    Delivered,
+    PartiallyDelivered,
+    RecallRequested,
+    Recalled,
+    Expired,
+    Consumed,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ComplianceStatus {
+    Compliant,
+    PendingReview,
+    NonCompliant,
+}
+
+impl ComplianceStatus {
+    /// Short badge printed on physical batch labels
+    pub fn badge(self) -> &'static str {
+        match self {
+            ComplianceStatus::Compliant => "EUDR-OK",
+            ComplianceStatus::PendingReview => "REVIEW",
+            ComplianceStatus::NonCompliant => "BLOCKED",
+        }
+    }
+    
+    /// Compliance status implied by a plot's deforestation risk
+    pub fn for_risk(risk: DeforestationRisk) -> ComplianceStatus {
+        match risk {
+            DeforestationRisk::Low => ComplianceStatus::Compliant,
+            DeforestationRisk::Medium => ComplianceStatus::PendingReview,
+            DeforestationRisk::High => ComplianceStatus::NonCompliant,
+        }
+    }
+    
+    /// Returns the less compliant of the two statuses
+    pub fn weakest(self, other: ComplianceStatus) -> ComplianceStatus {
+        fn rank(status: ComplianceStatus) -> u8 {
+            match status {
+                ComplianceStatus::Compliant => 0,
+                ComplianceStatus::PendingReview => 1,
+                ComplianceStatus::NonCompliant => 2,
+            }
+        }
+        if rank(other) > rank(self) { other } else { self }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum WeightUnit {
+    Gram,
+    Kilogram,
+    Tonne,
+}
+
+impl WeightUnit {
+    /// Normalizes a reported weight to whole kilograms. Grams round to the nearest
+    /// kilogram; the exact figure is kept on the batch as `reported_weight`
+    pub fn to_kg(self, amount: u64) -> Result<u64> {
+        let weight_kg = match self {
+            WeightUnit::Gram => amount / 1_000 + u64::from(amount % 1_000 >= 500),
+            WeightUnit::Kilogram => amount,
+            WeightUnit::Tonne => amount.checked_mul(1_000).ok_or(ErrorCode::WeightOverflow)?,
+        };
+        require!(weight_kg > 0, ErrorCode::InvalidWeight);
+        Ok(weight_kg)
+    }
+}
+
+/// Reasons a batch or its plot fails EUDR eligibility
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum EligibilityFailure {
+    PlotInactive,
+    NoVerificationOnRecord,
+    ScoreBelowThreshold,
+    HighDeforestationRisk,
+    VerificationExpired,
+    MissingPolygon,
+    BatchRecalled,
+    BatchNonCompliant,
+    PlotUnderReview,
+    BatchConsumed,
+}
+
+impl EligibilityFailure {
+    pub fn error(self) -> ErrorCode {
+        match self {
+            EligibilityFailure::PlotInactive
+            | EligibilityFailure::ScoreBelowThreshold
+            | EligibilityFailure::HighDeforestationRisk
+            | EligibilityFailure::BatchNonCompliant
+            | EligibilityFailure::PlotUnderReview => ErrorCode::NonCompliantFarm,
+            EligibilityFailure::VerificationExpired => ErrorCode::VerificationExpired,
+            EligibilityFailure::MissingPolygon => ErrorCode::MissingPolygon,
+            EligibilityFailure::NoVerificationOnRecord => ErrorCode::NoVerificationOnRecord,
+            EligibilityFailure::BatchRecalled => ErrorCode::InvalidStatusTransition,
+            EligibilityFailure::BatchConsumed => ErrorCode::BatchConsumed,
+        }
+    }
+    
+    pub fn describe(self) -> &'static str {
+        match self {
+            EligibilityFailure::PlotInactive => "plot is inactive",
+            EligibilityFailure::NoVerificationOnRecord => "no satellite verification on record",
+            EligibilityFailure::ScoreBelowThreshold => "compliance score below commodity threshold",
+            EligibilityFailure::HighDeforestationRisk => "high deforestation risk detected",
+            EligibilityFailure::VerificationExpired => "verification expired",
+            EligibilityFailure::MissingPolygon => "missing plot polygon",
+            EligibilityFailure::BatchRecalled => "batch is recalled",
+            EligibilityFailure::BatchNonCompliant => "batch is non-compliant",
+            EligibilityFailure::PlotUnderReview => "plot is under review or disputed",
+            EligibilityFailure::BatchConsumed => "batch is consumed",
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationType {
+    Satellite,
+    Audit,
+    Manual,
+}
+
+/// Smallholder plots take the EUDR simplified path, which waives the polygon requirement
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DueDiligencePath {
+    Standard,
+    SimplifiedDueDiligence,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    Registered,
+    Verified,
+    BaselineRecorded,
+    HarvestRegistered,
+    Expired,
+    Deactivated,
+    Reactivated,
+    Contested,
+    CoordinatesRevised,
+    PiiRedacted,
+}
+
+/// A sensitive config change that must wait out the timelock
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum ConfigChange {
+    CommodityPolicy {
+        commodity_type: CommodityType,
+        params: PolicyParams,
+    },
+    StringLimits(StringLimits),
+    SmallholderThreshold(f64),
+    OracleStakeRequirement(u64),
+    Timelock(i64),
+    Paused(bool),
+    ChallengeRequired(bool),
+}
+
+impl ConfigChange {
+    /// Same bounds the direct setters enforce, checked when the change is proposed
+    pub fn validate(&self) -> Result<()> {
+        match *self {
+            ConfigChange::CommodityPolicy { params, .. } => params.validate()?,
+            ConfigChange::StringLimits(string_limits) => {
+                require!(string_limits.within_ceilings(), ErrorCode::StringLimitAboveCeiling)
+            }
+            ConfigChange::SmallholderThreshold(threshold_hectares) => require!(
+                threshold_hectares.is_finite() && threshold_hectares > 0.0,
+                ErrorCode::InvalidArea
+            ),
+            ConfigChange::OracleStakeRequirement(_) => {}
+            ConfigChange::Timelock(delay_seconds) => {
+                require!(delay_seconds >= 0, ErrorCode::InvalidConfigChange)
+            }
+            ConfigChange::Paused(_) | ConfigChange::ChallengeRequired(_) => {}
+        }
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum PlotGeometry {
+    Point,
+    Polygon,
+}
+
+impl PlotGeometry {
+    /// Point for point + radius coordinates; polygons and polygon hashes are Polygon
+    pub fn of(coordinates: &str) -> PlotGeometry {
+        if parse_point_radius(coordinates).is_some() {
+            PlotGeometry::Point
+        } else {
+            PlotGeometry::Polygon
+        }
+    }
+}
+
+// ============================================================================
+// Events (for indexing and monitoring)
+// ============================================================================
+
+#[event]
+pub struct FarmPlotRegistered {
+    pub plot_id: String,
+    pub farmer: Pubkey,
+    pub coordinates: String,
+    pub centroid_lat_microdeg: Option<i32>,
+    pub centroid_lon_microdeg: Option<i32>,
+    pub country_code: [u8; 2],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct HarvestBatchRegistered {
+    pub batch_id: String,
+    pub farm_plot: Pubkey,
+    pub weight_kg: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BatchStatusUpdated {
+    pub batch_id: String,
+    pub new_status: BatchStatus,
+    pub destination: String,
+    pub update_seq: u32,
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-#[instruction(verification_hash: String, no_deforestation: bool, verification_timestamp: i64)]
-pub struct RecordSatelliteVerification<'info> {
-    #[account(
-        init,
-        payer = verifier,
-        space = 8 + 180,
-        seeds = [
-            b"verification",
-            farm_plot.key().as_ref(),
-            verifier.key().as_ref(),
-            &verification_timestamp.to_le_bytes()
-        ],
-        bump
-    )]
-    pub verification: Account<'info, SatelliteVerification>,
-    
-    #[account(
-        mut,
-        seeds = [b"farm_plot", farm_plot.plot_id.as_bytes(), farm_plot.farmer.as_ref()],
-        bump = farm_plot.bump
-    )]
-    pub farm_plot: Account<'info, FarmPlot>,
-    
-    #[account(mut)]
-    pub verifier: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct SatelliteVerificationRecorded {
+    pub farm_plot: Pubkey,
+    pub verification_hash: String,
+    pub compliant: bool,
+    pub timestamp: i64,
+    pub provider: String,
+    pub imagery_date: i64,
 }
 
+#[event]
+pub struct ProcessingRecorded {
+    pub batch_id: String,
+    pub processing_type: String,
+    pub input_weight_kg: u64,
+    pub output_weight_kg: u64,
+    pub yield_basis_points: u16,
+    pub timestamp: i64,
+}
 
-#[derive(Accounts)]
-pub struct GenerateDDSData<'info> {
-    #[account(
-        seeds = [b"harvest_batch", harvest_batch.batch_id.as_bytes(), harvest_batch.farmer.as_ref()],
-        bump = harvest_batch.bump
-    )]
-    pub harvest_batch: Account<'info, HarvestBatch>,
-    
-    #[account(
-        seeds = [b"farm_plot", farm_plot.plot_id.as_bytes(), farm_plot.farmer.as_ref()],
-        bump = farm_plot.bump
-    )]
-    pub farm_plot: Account<'info, FarmPlot>,
+#[event]
+pub struct DDSReportGenerated {
+    pub batch_id: String,
+    pub compliance_score: u8,
+    pub timestamp: i64,
 }
 
-// ============================================================================
-// Enums
-// ============================================================================
+#[event]
+pub struct StorageLotCreated {
+    pub lot_id: String,
+    pub operator: Pubkey,
+    pub commodity_type: CommodityType,
+    pub timestamp: i64,
+}
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
-pub enum CommodityType {
-    Cocoa,
-    Coffee,
-    PalmOil,
-    Soy,
-    Cattle,
-    Rubber,
-    Timber,
+#[event]
+pub struct LotDeposited {
+    pub lot_id: String,
+    pub batch_id: String,
+    pub weight_kg: u64,
+    pub total_weight_kg: u64,
+    pub blended_status: ComplianceStatus,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LotWithdrawn {
+    pub lot_id: String,
+    pub weight_kg: u64,
+    pub remaining_weight_kg: u64,
+    pub blended_status: ComplianceStatus,
+    pub destination: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerificationChallengeIssued {
+    pub farm_plot: Pubkey,
+    pub verifier: Pubkey,
+    pub nonce: [u8; 32],
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct LandCoverBaselineRecorded {
+    pub farm_plot: Pubkey,
+    pub canopy_cover_bps: u16,
+    pub land_cover_hash: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CommodityPolicyUpdated {
+    pub commodity_type: CommodityType,
+    pub min_compliance_score: u8,
+    pub verification_validity_seconds: i64,
+    pub min_verification_interval_seconds: i64,
+    pub max_yield_kg_per_hectare: u64,
+    pub requires_cosigner: bool,
+    pub min_age_before_harvest_seconds: i64,
+    pub smallholder_min_age_before_harvest_seconds: i64,
+    pub allow_provisional_medium_risk: bool,
+    pub verifier_tier_required: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ComplianceScoreChanged {
+    pub farm_plot: Pubkey,
+    pub old_score: u8,
+    pub new_score: u8,
+    pub old_risk: DeforestationRisk,
+    pub new_risk: DeforestationRisk,
+    pub reason: String,
+}
+
+#[event]
+pub struct DDSReferenceAssigned {
+    pub batch_id: String,
+    pub reference_number: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GpsDeviceApprovalChanged {
+    pub device: Pubkey,
+    pub approved: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OracleApprovalChanged {
+    pub oracle: Pubkey,
+    pub approved: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CosignerApprovalChanged {
+    pub cosigner: Pubkey,
+    pub approved: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DDSRecorded {
+    pub batch_id: String,
+    pub report_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BatchClosed {
+    pub batch_id: String,
+}
+
+#[event]
+pub struct BatchRecalled {
+    pub batch_id: String,
+    pub reason: String,
+}
+
+#[event]
+pub struct RecallResolved {
+    pub batch_id: String,
+    pub finalized: bool,
+    pub status: BatchStatus,
+}
+
+#[event]
+pub struct BatchAttributesUpdated {
+    pub batch_id: String,
+    pub attribute_count: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerificationExpired {
+    pub farm_plot: Pubkey,
+    pub expired_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BatchPlotsRegistered {
+    pub farmer: Pubkey,
+    pub count: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StringLimitsUpdated {
+    pub string_limits: StringLimits,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ExternalIdAdded {
+    pub batch_id: String,
+    pub system: String,
+    pub id: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FarmPlotDeactivated {
+    pub farm_plot: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FarmPlotReactivated {
+    pub farm_plot: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DerivativeCreated {
+    pub batch_id: String,
+    pub index: u32,
+    pub product_name: String,
+    pub output_weight_kg: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SatelliteVerificationBatchRecorded {
+    pub verifier: Pubkey,
+    pub verification_hash: String,
+    pub plot_count: u32,
+    pub compliant_count: u32,
+    pub non_compliant_count: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PlotCheckpointed {
+    pub farm_plot: Pubkey,
+    pub digest: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CropAdded {
+    pub farm_plot: Pubkey,
+    pub commodity_type: CommodityType,
+    pub area_hectares: f64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerificationContested {
+    pub farm_plot: Pubkey,
+    pub original: Pubkey,
+    pub contest: Pubkey,
+    pub disagrees: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BatchMarkedExpired {
+    pub batch_id: String,
+    pub expired_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CertifierApprovalChanged {
+    pub certifier: Pubkey,
+    pub accreditation_id: String,
+    pub approved: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CertificationRecorded {
+    pub certifier: Pubkey,
+    pub farm_plot: Pubkey,
+    pub subject: Pubkey,
+    pub cert_type: String,
+    pub valid_from: i64,
+    pub valid_until: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OracleStakeChanged {
+    pub oracle: Pubkey,
+    pub stake_remaining: u64,
+    pub slashed_lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PlotCoordinatesRevised {
+    pub farm_plot: Pubkey,
+    pub revision: u16,
+    pub reason: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BatchFinalizedForExport {
+    pub batch_id: String,
+    pub lot_number: String,
+    pub packaging_units: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProgramPauseChanged {
+    pub paused: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ChallengeRequirementChanged {
+    pub required: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigChangeProposed {
+    pub change: ConfigChange,
+    pub effective_at: i64,
+    pub timestamp: i64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
-pub enum DeforestationRisk {
-    Low,
-    Medium,
-    High,
+#[event]
+pub struct ConfigChangeApplied {
+    pub change: ConfigChange,
+    pub timestamp: i64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
-pub enum BatchStatus {
-    Harvested,
-    Processing,
-    InTransit,
-    Delivered,
+#[event]
+pub struct BatchOwnershipTransferred {
+    pub batch_id: String,
+    pub previous_owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub timestamp: i64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
-pub enum ComplianceStatus {
-    Compliant,
-    PendingReview,
-    NonCompliant,
+#[event]
+pub struct FarmerSuspensionChanged {
+    pub farmer: Pubkey,
+    pub suspended: bool,
+    pub timestamp: i64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
-pub enum VerificationType {
-    Satellite,
-    Audit,
-    Manual,
+#[event]
+pub struct InputMixRecorded {
+    pub mix_id: String,
+    pub processor: Pubkey,
+    pub total_weight_kg: u64,
+    pub weighted_compliance_score: u8,
+    pub tainted_input_count: u8,
+    pub compliance_status: ComplianceStatus,
+    pub timestamp: i64,
 }
 
-// ============================================================================
-// Events (for indexing and monitoring)
-// ============================================================================
+#[event]
+pub struct PaymentRecorded {
+    pub batch_id: String,
+    pub payer: Pubkey,
+    pub paid_to: Pubkey,
+    pub amount: u64,
+    pub currency: String,
+    pub timestamp: i64,
+}
 
 #[event]
-pub struct FarmPlotRegistered {
+pub struct SeasonReset {
     pub plot_id: String,
-    pub farmer: Pubkey,
-    pub coordinates: String,
+    pub season_number: u16,
+    pub season_harvested_kg: u64,       // total of the season just closed
+    pub reset_by: Pubkey,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct HarvestBatchRegistered {
-    pub batch_id: String,
-    pub farm_plot: Pubkey,
-    pub weight_kg: u64,
+pub struct AttestationSigned {
+    pub plot_id: String,
+    pub attestation: Pubkey,
+    pub attestation_hash: [u8; 32],
+    pub attestor: Pubkey,
+    pub signature: [u8; 64],
     pub timestamp: i64,
 }
 
 #[event]
-pub struct BatchStatusUpdated {
-    pub batch_id: String,
-    pub new_status: BatchStatus,
-    pub destination: String,
+pub struct AttestorApprovalChanged {
+    pub attestor: Pubkey,
+    pub approved: bool,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct SatelliteVerificationRecorded {
+pub struct OracleTierChanged {
+    pub oracle: Pubkey,
+    pub previous_tier: u8,
+    pub tier: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FarmerPiiRedacted {
     pub farm_plot: Pubkey,
-    pub verification_hash: String,
-    pub compliant: bool,
+    pub redacted_by: Pubkey,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct DDSReportGenerated {
+pub struct HarvestBatchConsumed {
     pub batch_id: String,
-    pub compliance_score: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DocumentsCommitted {
+    pub batch_id: String,
+    pub previous_root: [u8; 32],        // zero on the first commit
+    pub documents_merkle_root: [u8; 32],
     pub timestamp: i64,
 }
 
@@ -454,6 +7866,234 @@ pub struct DDSReport {
     pub compliance_score: u8,
     pub last_verified: i64,
     pub registration_timestamp: i64,
+    pub processing: Option<ProcessingStep>,
+    // Fields below were appended in DDS_VERSION 2; older readers can stop here
+    pub dds_version: u8,
+    pub area_hectares: f64,
+    pub verification_count: u32,
+    pub deforestation_risk: DeforestationRisk,
+    // Appended in DDS_VERSION 3
+    pub head_count: Option<u32>,
+    // Appended in DDS_VERSION 4
+    pub sustainability_attrs: Vec<BatchAttribute>,
+    // Appended in DDS_VERSION 5
+    pub country_code: [u8; 2],
+    // Appended in DDS_VERSION 6
+    pub external_ids: Vec<ExternalId>,
+    // Appended in DDS_VERSION 7
+    pub due_diligence_path: DueDiligencePath,
+    // Appended in DDS_VERSION 8
+    pub total_verification_spend: u64,
+    // Appended in DDS_VERSION 9
+    pub batch_expires_at: Option<i64>,
+    pub batch_expired: bool,
+    // Appended in DDS_VERSION 10
+    pub certifications: Vec<CertificationSummary>,
+    // Appended in DDS_VERSION 11
+    pub pending_review: bool,
+    // Appended in DDS_VERSION 12
+    pub lot_number: String,
+    pub packaging_units: u32,
+    // Appended in DDS_VERSION 13; set when a verification account is supplied
+    pub verification_provider: Option<String>,
+    pub imagery_date: Option<i64>,
+    // Appended in DDS_VERSION 14
+    pub geometry_type: PlotGeometry,
+    // Appended in DDS_VERSION 15; set when the batch's Payment account is supplied
+    pub payment_confirmed: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CertificationSummary {
+    pub certifier: Pubkey,
+    pub cert_type: String,
+    pub batch_level: bool,
+    pub valid_from: i64,
+    pub valid_until: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ProcessingStep {
+    pub processing_type: String,
+    pub input_weight_kg: u64,
+    pub output_weight_kg: u64,
+    pub yield_basis_points: u16,
+    pub processed_at: i64,
+}
+
+/// Ed25519 signature by an approved GPS device over the plot's coordinate bytes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct GpsAttestation {
+    pub device_pubkey: Pubkey,
+    pub device_signature: [u8; 64],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ComplianceSnapshot {
+    pub plot_id: String,
+    pub raw_score: u8,
+    pub effective_score: u8,
+    pub risk: DeforestationRisk,
+    pub last_verified: i64,
+    pub days_since_verification: u32,
+    pub is_batch_eligible: bool,
+}
+
+/// Key/value sustainability claim attached to a batch, e.g. ("organic", "true")
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BatchAttribute {
+    pub key: String,
+    pub value: String,
+}
+
+/// Identifier for the batch in an external system, e.g. ("gs1", GTIN)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ExternalId {
+    pub system: String,
+    pub id: String,
+}
+
+/// Plot registration fields, as accepted by register_plots_batch
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PlotRegistrationInput {
+    pub plot_id: String,
+    pub farmer_name: String,
+    pub location: String,
+    pub country_code: [u8; 2],
+    pub coordinates: String,
+    pub area_hectares: f64,
+    pub commodity_type: CommodityType,
+    pub commodity_label: String,
+    pub registration_timestamp: i64,
+    pub harvest_season: Option<HarvestSeason>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BatchProvenance {
+    pub batch_id: String,
+    pub status: BatchStatus,
+    pub weight_kg: u64,
+    pub remaining_weight_kg: u64,
+    pub harvest_timestamp: i64,
+    pub destination: String,
+    pub source_plots: Vec<Pubkey>,
+    pub plot_id: String,
+    pub farmer: Pubkey,
+    pub country_code: [u8; 2],
+    pub commodity_type: CommodityType,
+    pub compliance_score: u8,
+    pub deforestation_risk: DeforestationRisk,
+    pub verifications: Vec<ProvenanceVerification>,
+    pub processing: Option<ProcessingStep>,
+    pub derivatives: Vec<ProvenanceDerivative>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ProvenanceVerification {
+    pub verifier: Pubkey,
+    pub verification_timestamp: i64,
+    pub no_deforestation: bool,
+    pub confidence: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ProvenanceDerivative {
+    pub index: u32,
+    pub product_name: String,
+    pub output_weight_kg: u64,
+    pub created_at: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct EligibilityResult {
+    pub eligible: bool,
+    pub failing_reasons: Vec<String>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct QrPayload {
+    pub plot_id: String,                // max 32
+    pub commodity: CommodityType,
+    pub country_code: [u8; 2],
+    pub compliance_score: u8,
+    pub batch: Pubkey,                  // harvest batch account, the on-chain lookup key
+    pub dds_reference: String,          // EUDR reference number, empty until assigned
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LabelPayload {
+    pub batch_id: String,               // max 32
+    pub commodity_name: String,
+    pub weight_kg: u64,
+    pub farmer_name: String,            // max 64
+    pub harvest_date_str: String,       // UTC, "YYYY-MM-DD"
+    pub compliance_badge: String,       // "EUDR-OK", "REVIEW" or "BLOCKED"
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CooperativeStats {
+    pub total_plots: u32,
+    pub total_hectares: f64,
+    pub avg_compliance: u16,            // mean compliance score, rounded down
+    pub flagged_count: u32,             // plots at High deforestation risk
+}
+
+/// Canonical (Borsh) snapshot of a plot's compliance state returned by sign_attestation
+/// A buyer checks the attestor's Ed25519 signature over these bytes against the
+/// attestor key they trust; no chain access is needed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PlotAttestation {
+    pub attestation_version: u8,
+    pub attestor: Pubkey,
+    pub farm_plot: Pubkey,
+    pub plot_id: String,
+    pub farmer: Pubkey,
+    pub commodity_type: CommodityType,
+    pub country_code: [u8; 2],
+    pub compliance_score: u8,
+    pub deforestation_risk: DeforestationRisk,
+    pub compliance_status: ComplianceStatus,
+    pub is_active: bool,
+    pub last_verified: i64,
+    pub verification_count: u32,
+    pub last_verification_hash: [u8; 32],
+    pub attested_at: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RouteHop {
+    pub update_seq: u32,
+    pub status: BatchStatus,
+    pub destination: String,            // max 64
+    pub delivered_weight_kg: Option<u64>,
+    pub timestamp: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RouteHistory {
+    pub batch: Pubkey,
+    pub hops: Vec<RouteHop>,            // ordered by timestamp, then update sequence
+}
+
+/// Verification result an oracle signs off-chain over its Borsh bytes. Nonces must
+/// increase per oracle
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct OracleMessage {
+    pub plot: Pubkey,
+    pub result: bool,                   // no_deforestation
+    pub confidence: u8,
+    pub nonce: u64,
+    pub expires_at: i64,
+    pub challenge_nonce: [u8; 32],      // nonce of the challenge being answered; zero if none
+    pub canopy_cover_bps: Option<u16>,  // observed canopy cover; required once the plot has a baseline
+}
+
+/// An OracleMessage with the oracle's Ed25519 signature, which a preceding Ed25519
+/// program instruction must verify
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SignedOracleMessage {
+    pub message: OracleMessage,
+    pub signature: [u8; 64],
 }
 
 // ============================================================================
@@ -464,9 +8104,9 @@ pub struct DDSReport {
 pub enum ErrorCode {
     #[msg("Farm is not compliant with EUDR requirements")]
     NonCompliantFarm,
-    #[msg("Plot ID is too long (max 32 characters)")]
+    #[msg("Plot ID is too long (max 32 bytes)")]
     PlotIdTooLong,
-    #[msg("Batch ID is too long (max 32 characters)")]
+    #[msg("Batch ID is too long (max 32 bytes)")]
     BatchIdTooLong,
     #[msg("Invalid coordinates format")]
     InvalidCoordinates,
@@ -478,4 +8118,577 @@ pub enum ErrorCode {
     DestinationTooLong,
     #[msg("Invalid verification hash")]
     InvalidHash,
-}
\ No newline at end of file
+    #[msg("Processing type is too long (max 48 bytes)")]
+    ProcessingTypeTooLong,
+    #[msg("Invalid yield (output weight exceeds input weight)")]
+    InvalidYield,
+    #[msg("Batch cannot move to the requested status")]
+    InvalidStatusTransition,
+    #[msg("Lot ID is too long (max 32 bytes)")]
+    LotIdTooLong,
+    #[msg("Commodity type does not match")]
+    CommodityMismatch,
+    #[msg("Withdrawal exceeds the lot's stored weight")]
+    InsufficientLotBalance,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+    #[msg("Challenge nonce does not match")]
+    ChallengeMismatch,
+    #[msg("Challenge has already been used")]
+    ChallengeAlreadyUsed,
+    #[msg("Challenge has expired")]
+    ChallengeExpired,
+    #[msg("Invalid bounding box")]
+    InvalidBoundingBox,
+    #[msg("Neighbor account is not a FarmTrace farm plot")]
+    InvalidNeighborAccount,
+    #[msg("Plot overlaps an existing registered plot")]
+    PlotOverlap,
+    #[msg("Invalid canopy cover (max 10000 basis points)")]
+    InvalidCanopyCover,
+    #[msg("Invalid compliance score (max 100)")]
+    InvalidComplianceScore,
+    #[msg("Invalid verification validity window (must be >= 0)")]
+    InvalidValidityWindow,
+    #[msg("Commodity policy account is invalid")]
+    InvalidPolicyAccount,
+    #[msg("Farm verification has expired under the commodity policy")]
+    VerificationExpired,
+    #[msg("Signer is not authorized for this action")]
+    Unauthorized,
+    #[msg("Commodity label is too long (max 32 bytes)")]
+    CommodityLabelTooLong,
+    #[msg("Commodity label is required for Other and must be empty otherwise")]
+    CommodityLabelRequired,
+    #[msg("Invalid DDS reference number (1-64 bytes)")]
+    InvalidDDSReference,
+    #[msg("Batch already has a DDS reference number")]
+    ReferenceAlreadyAssigned,
+    #[msg("Batch's DDS must be recorded before a reference number is assigned")]
+    DDSNotRecorded,
+    #[msg("Delivered weight exceeds the batch's remaining weight")]
+    OverDelivery,
+    #[msg("GPS device is not approved")]
+    DeviceNotApproved,
+    #[msg("GPS device signature is missing or invalid")]
+    InvalidDeviceSignature,
+    #[msg("Batch has not been delivered")]
+    BatchNotDelivered,
+    #[msg("A DDS record must exist before the batch can be closed")]
+    DDSRequiredBeforeClose,
+    #[msg("Failed to serialize report")]
+    SerializationFailed,
+    #[msg("A plot with this ID is already registered by this farmer")]
+    PlotAlreadyRegistered,
+    #[msg("Invalid source plots for a multi-plot batch")]
+    InvalidSourcePlots,
+    #[msg("Harvest season must start before it ends")]
+    InvalidHarvestSeason,
+    #[msg("No verification within the plot's harvest season")]
+    NoInSeasonVerification,
+    #[msg("Invalid recall reason (1-64 bytes)")]
+    InvalidRecallReason,
+    #[msg("Batch has no pending recall")]
+    NoPendingRecall,
+    #[msg("Farmer name is too long (max 64 bytes)")]
+    FarmerNameTooLong,
+    #[msg("Location is too long (max 64 bytes)")]
+    LocationTooLong,
+    #[msg("Head count and ear tags are only allowed for cattle")]
+    CattleFieldsNotAllowed,
+    #[msg("Number of ear tags does not match head count")]
+    EarTagCountMismatch,
+    #[msg("Too many ear tags for one batch")]
+    TooManyEarTags,
+    #[msg("Invalid ear tag ID (1-24 bytes)")]
+    InvalidEarTag,
+    #[msg("Too many sustainability attributes")]
+    TooManyAttributes,
+    #[msg("Attribute key is not in the allowed list")]
+    UnknownAttributeKey,
+    #[msg("Attribute value is too long (max 32 bytes)")]
+    AttributeValueTooLong,
+    #[msg("Plot metadata hash does not match")]
+    MetadataHashMismatch,
+    #[msg("Invalid ISO 3166-1 alpha-2 country code")]
+    InvalidCountryCode,
+    #[msg("Plot verification has not expired yet")]
+    VerificationNotExpired,
+    #[msg("Plot verification is already marked expired")]
+    AlreadyMarkedExpired,
+    #[msg("Invalid plot batch (1-10 plots, one PDA per plot)")]
+    InvalidPlotBatch,
+    #[msg("Invalid confidence (max 100)")]
+    InvalidConfidence,
+    #[msg("Provenance account does not belong to this batch or plot")]
+    InvalidProvenanceAccount,
+    #[msg("Harvest is dated before the plot was registered")]
+    HarvestBeforeRegistration,
+    #[msg("Harvest is dated in the future")]
+    HarvestInFuture,
+    #[msg("Weight overflows when converted to kilograms")]
+    WeightOverflow,
+    #[msg("Polygon has too many vertices for the plot's area")]
+    TooManyVertices,
+    #[msg("Plot has no polygon on record")]
+    MissingPolygon,
+    #[msg("String limit exceeds the space reserved in the account")]
+    StringLimitAboveCeiling,
+    #[msg("External system must be one of gs1, epcis, traces")]
+    UnknownExternalSystem,
+    #[msg("External id must be 1-64 bytes")]
+    InvalidExternalId,
+    #[msg("Batch already has an id for this external system")]
+    ExternalIdAlreadySet,
+    #[msg("Plots at or above the smallholder threshold must register a polygon")]
+    PolygonRequired,
+    #[msg("Plot was verified too recently; wait for the policy's minimum interval")]
+    VerificationTooSoon,
+    #[msg("Harvested weight exceeds the plot's plausible yield for the season")]
+    ImplausibleYield,
+    #[msg("Farm plot is already inactive")]
+    PlotAlreadyInactive,
+    #[msg("Farm plot is already active")]
+    PlotAlreadyActive,
+    #[msg("This commodity requires an approved cosigner on registration")]
+    CosignerRequired,
+    #[msg("Payer balance can't cover rent for the new accounts; see logs for the amount needed")]
+    InsufficientRentBalance,
+    #[msg("Product name must be 1-32 bytes")]
+    InvalidProductName,
+    #[msg("Verification batch accounts don't match the plots and results supplied")]
+    InvalidVerificationBatch,
+    #[msg("Signer does not own this farm plot")]
+    NotPlotOwner,
+    #[msg("Hash must be a 32-byte digest in hex (64 chars) or base58")]
+    MalformedHash,
+    #[msg("Weight exceeds what the batch's registered harvest can back")]
+    UnbackedWeight,
+    #[msg("Plot is too new to register batches; wait out the policy's grace period")]
+    PlotTooNew,
+    #[msg("Crop allocations would exceed the plot's area")]
+    AreaAllocationExceeded,
+    #[msg("Oracle is not registered or has been revoked")]
+    OracleNotRegistered,
+    #[msg("Only the plot's latest verification can be contested")]
+    NotLatestVerification,
+    #[msg("A verification can't be contested by the oracle that submitted it")]
+    SameOracle,
+    #[msg("Batch is past its expiry")]
+    BatchExpired,
+    #[msg("Batch has no expiry or hasn't reached it yet")]
+    BatchNotExpired,
+    #[msg("Batch expiry must be after its harvest")]
+    InvalidBatchExpiry,
+    #[msg("Certifier name and accreditation ID must be non-empty and within length limits")]
+    InvalidCertifierDetails,
+    #[msg("Certifier is not in the accredited registry")]
+    CertifierNotAccredited,
+    #[msg("Certification type must be non-empty and at most 32 bytes")]
+    InvalidCertificationType,
+    #[msg("Certification subject must be the farm plot or one of its batches")]
+    InvalidCertificationSubject,
+    #[msg("Account is not a certification for this batch or its plot")]
+    InvalidCertificationAccount,
+    #[msg("Harvest timestamp precedes the plot's latest registered harvest")]
+    HarvestOutOfOrder,
+    #[msg("Oracle stake is below the required amount")]
+    InsufficientOracleStake,
+    #[msg("Slash requires a disagreeing contest of one of the oracle's verifications")]
+    InvalidSlash,
+    #[msg("New coordinates are identical to the current ones")]
+    CoordinatesUnchanged,
+    #[msg("Revision reason must be non-empty and at most 64 bytes")]
+    InvalidRevisionReason,
+    #[msg("Batch is finalized for export and its weights are locked")]
+    BatchLocked,
+    #[msg("Lot number must be non-empty and at most 32 bytes")]
+    InvalidLotNumber,
+    #[msg("Packaging unit count must be greater than zero")]
+    InvalidPackagingUnits,
+    #[msg("Config timelock has not elapsed yet")]
+    TimelockNotElapsed,
+    #[msg("Config timelock is active; propose this change instead")]
+    TimelockRequired,
+    #[msg("Invalid config change")]
+    InvalidConfigChange,
+    #[msg("Imagery provider must be non-empty and at most 32 bytes")]
+    InvalidImagerySource,
+    #[msg("Imagery date cannot be in the future")]
+    ImageryDateInFuture,
+    #[msg("New owner must differ from the current owner")]
+    InvalidOwnershipTransfer,
+    #[msg("Verification index segment is full")]
+    VerificationIndexFull,
+    #[msg("Range start must not be after its end")]
+    InvalidTimeRange,
+    #[msg("Too many verifications in range; narrow the window")]
+    RangeTooWide,
+    #[msg("Farmer is suspended")]
+    FarmerSuspended,
+    #[msg("Farmer is not suspended")]
+    FarmerNotSuspended,
+    #[msg("Invalid input mix: check the mix ID, input count and batch/plot accounts")]
+    InvalidInputMix,
+    #[msg("Point radius must be positive and consistent with the plot's area")]
+    InvalidPointRadius,
+    #[msg("A verification for this imagery has already been recorded")]
+    VerificationAlreadyRecorded,
+    #[msg("Invalid payment: amount, currency or recipient")]
+    InvalidPayment,
+    #[msg("Attestation time must be within clock skew of the cluster clock")]
+    InvalidAttestationTime,
+    #[msg("Plot has no satellite verification on record")]
+    NoVerificationOnRecord,
+    #[msg("Verifier's oracle tier is below the commodity policy's requirement")]
+    InsufficientVerifierTier,
+    #[msg("Harvest batch was not registered against this farm plot")]
+    BatchPlotMismatch,
+    #[msg("Plot's personal data has already been redacted")]
+    AlreadyRedacted,
+    #[msg("Batch has no traceable weight left and is consumed")]
+    BatchConsumed,
+    #[msg("No document merkle root is committed, or the root is zero")]
+    InvalidDocumentRoot,
+    #[msg("Merkle proof exceeds the maximum depth")]
+    ProofTooLong,
+    #[msg("Document is not included under the committed merkle root")]
+    DocumentNotIncluded,
+    #[msg("Oracle message doesn't match the submission or its Ed25519 signature check")]
+    InvalidOracleMessage,
+    #[msg("Oracle message has expired")]
+    OracleMessageExpired,
+    #[msg("Oracle message nonce has already been used")]
+    NonceReused,
+    #[msg("Attestor is not approved")]
+    AttestorNotApproved,
+    #[msg("Attestation isn't signed by the attestor over the plot's current state")]
+    InvalidAttestationSignature,
+    #[msg("Program is paused")]
+    ProgramPaused,
+    #[msg("Verification timestamp is in the future")]
+    VerificationInFuture,
+    #[msg("A verification challenge must be issued and answered")]
+    ChallengeRequired,
+    #[msg("Plot has a land-cover baseline, so the oracle must report canopy cover")]
+    CanopyReadingRequired,
+    #[msg("Canopy loss against the baseline contradicts a no-deforestation result")]
+    CanopyLossAgainstBaseline,
+    #[msg("Batch ID has a DDS on record from an earlier batch and cannot be reused")]
+    BatchIdRetired,
+    #[msg("Processing record is not the batch's latest processing step")]
+    ProcessingStepNotLatest,
+    #[msg("A plot's farmer cannot verify their own plot")]
+    FarmerCannotVerify,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// A zeroed plot, decoded the way register_plots_batch decodes a fresh account
+    fn blank_plot() -> FarmPlot {
+        FarmPlot::try_deserialize_unchecked(&mut &[0u8; FARM_PLOT_SPACE][..]).unwrap()
+    }
+    
+    #[test]
+    fn compliance_score_saturates_at_zero() {
+        let mut farm_plot = blank_plot();
+        farm_plot.compliance_score = 80;
+        set_compliance_score(&mut farm_plot, -50);
+        assert_eq!(farm_plot.compliance_score, 0);
+    }
+    
+    #[test]
+    fn compliance_score_saturates_at_one_hundred() {
+        let mut farm_plot = blank_plot();
+        set_compliance_score(&mut farm_plot, 300);
+        assert_eq!(farm_plot.compliance_score, 100);
+    }
+    
+    #[test]
+    fn compliance_score_keeps_in_range_values() {
+        let mut farm_plot = blank_plot();
+        for score in [0, 1, 50, 99, 100] {
+            set_compliance_score(&mut farm_plot, score);
+            assert_eq!(i32::from(farm_plot.compliance_score), score);
+        }
+    }
+    
+    /// Feeds `readings` of (verification score, weight) through the EMA from `start`
+    fn ema_trajectory(start: u8, readings: &[(u8, u8)]) -> Vec<i32> {
+        let mut score = start;
+        readings
+            .iter()
+            .map(|&(verification_score, weight_percent)| {
+                score = blend_compliance_score(score, verification_score, weight_percent) as u8;
+                i32::from(score)
+            })
+            .collect()
+    }
+    
+    #[test]
+    fn ema_recovers_to_one_hundred_on_clean_results() {
+        let trajectory = ema_trajectory(50, &[(100, 100); 7]);
+        assert_eq!(trajectory, vec![75, 88, 94, 97, 99, 100, 100]);
+    }
+    
+    #[test]
+    fn ema_does_not_stall_one_point_short() {
+        assert_eq!(blend_compliance_score(99, 100, 100), 100);
+        assert_eq!(blend_compliance_score(1, 0, 80), 0);
+    }
+    
+    #[test]
+    fn ema_decays_to_zero_on_confident_detections() {
+        let trajectory = ema_trajectory(100, &[(0, 80); 11]);
+        assert_eq!(trajectory, vec![60, 36, 22, 13, 8, 5, 3, 2, 1, 0, 0]);
+    }
+    
+    #[test]
+    fn ema_step_scales_with_confidence() {
+        assert_eq!(blend_compliance_score(100, 0, 100), 50);
+        assert_eq!(blend_compliance_score(100, 0, 40), 80);
+        assert_eq!(blend_compliance_score(100, 0, 0), 100);
+    }
+    
+    fn verified_plot(last_verified: i64) -> FarmPlot {
+        let mut farm_plot = blank_plot();
+        farm_plot.verification_count = 1;
+        farm_plot.last_verified = last_verified;
+        farm_plot
+    }
+    
+    fn policy_with_interval(min_verification_interval_seconds: i64) -> CommodityPolicy {
+        let mut policy = CommodityPolicy::default_for(CommodityType::Cocoa);
+        policy.min_verification_interval_seconds = min_verification_interval_seconds;
+        policy
+    }
+    
+    #[test]
+    fn verification_inside_cooldown_is_too_soon() {
+        let farm_plot = verified_plot(1_000_000);
+        let policy = policy_with_interval(SECONDS_PER_DAY);
+        assert_eq!(
+            check_verification_interval(&farm_plot, &policy, 1_000_000 + SECONDS_PER_DAY - 1).unwrap_err(),
+            ErrorCode::VerificationTooSoon.into()
+        );
+    }
+    
+    #[test]
+    fn verification_after_cooldown_is_accepted() {
+        let farm_plot = verified_plot(1_000_000);
+        let policy = policy_with_interval(SECONDS_PER_DAY);
+        assert!(check_verification_interval(&farm_plot, &policy, 1_000_000 + SECONDS_PER_DAY).is_ok());
+    }
+    
+    #[test]
+    fn first_verification_skips_cooldown() {
+        let mut farm_plot = verified_plot(0);
+        farm_plot.verification_count = 0;
+        let policy = policy_with_interval(SECONDS_PER_DAY);
+        assert!(check_verification_interval(&farm_plot, &policy, 1).is_ok());
+    }
+    
+    fn plot_with_baseline(baseline_bps: u16) -> FarmPlot {
+        let mut farm_plot = blank_plot();
+        farm_plot.baseline_canopy_cover_bps = Some(baseline_bps);
+        farm_plot
+    }
+    
+    #[test]
+    fn canopy_change_is_measured_against_the_baseline() {
+        let farm_plot = plot_with_baseline(8_000);
+        assert_eq!(canopy_change_from_baseline(&farm_plot, Some(7_500), true).unwrap(), Some(-500));
+        assert_eq!(canopy_change_from_baseline(&farm_plot, Some(8_200), true).unwrap(), Some(200));
+    }
+    
+    #[test]
+    fn clean_result_cannot_hide_canopy_loss() {
+        let farm_plot = plot_with_baseline(8_000);
+        assert_eq!(
+            canopy_change_from_baseline(&farm_plot, Some(6_000), true).unwrap_err(),
+            ErrorCode::CanopyLossAgainstBaseline.into()
+        );
+        assert_eq!(canopy_change_from_baseline(&farm_plot, Some(6_000), false).unwrap(), Some(-2_000));
+    }
+    
+    #[test]
+    fn baseline_plots_require_a_canopy_reading() {
+        assert_eq!(
+            canopy_change_from_baseline(&plot_with_baseline(8_000), None, true).unwrap_err(),
+            ErrorCode::CanopyReadingRequired.into()
+        );
+        assert_eq!(canopy_change_from_baseline(&blank_plot(), None, true).unwrap(), None);
+    }
+    
+    fn described_plot(farmer_name: &str, location: &str) -> FarmPlot {
+        let mut farm_plot = blank_plot();
+        farm_plot.plot_id = "GH-001".to_string();
+        farm_plot.farmer_name = farmer_name.to_string();
+        farm_plot.location = location.to_string();
+        farm_plot.coordinates = "[[5.1,-1.2],[5.1,-1.1],[5.2,-1.1]]".to_string();
+        farm_plot.area_hectares = 2.5;
+        farm_plot.registration_timestamp = 1_700_000_000;
+        farm_plot
+    }
+    
+    #[test]
+    fn metadata_hash_is_deterministic() {
+        let farm_plot = described_plot("Ama", "Ashanti");
+        assert_eq!(plot_metadata_hash(&farm_plot), plot_metadata_hash(&farm_plot.clone()));
+    }
+    
+    #[test]
+    fn modified_plot_field_changes_metadata_hash() {
+        let original = described_plot("Ama", "Ashanti");
+        let mut tampered = original.clone();
+        tampered.area_hectares = 25.0;
+        assert_ne!(plot_metadata_hash(&original), plot_metadata_hash(&tampered));
+        
+        let mut tampered = original.clone();
+        tampered.coordinates.push_str(" ");
+        assert_ne!(plot_metadata_hash(&original), plot_metadata_hash(&tampered));
+    }
+    
+    #[test]
+    fn metadata_hash_separates_adjacent_strings() {
+        assert_ne!(
+            plot_metadata_hash(&described_plot("AB", "C")),
+            plot_metadata_hash(&described_plot("A", "BC"))
+        );
+    }
+    
+    fn staked_config(oracle_stake_lamports: u64) -> ProgramConfig {
+        let mut config = ProgramConfig::try_deserialize_unchecked(&mut &[0u8; 8 + 512][..]).unwrap();
+        config.oracle_stake_lamports = oracle_stake_lamports;
+        config
+    }
+    
+    fn oracle_with_stake(stake_remaining: u64) -> RegisteredOracle {
+        let mut registered = RegisteredOracle::try_deserialize_unchecked(&mut &[0u8; 8 + 256][..]).unwrap();
+        registered.is_active = true;
+        registered.stake_remaining = stake_remaining;
+        registered
+    }
+    
+    #[test]
+    fn oracle_standing_requires_the_configured_stake() {
+        let config = staked_config(1_000);
+        assert!(check_oracle_standing(Some(&oracle_with_stake(1_000)), &config).is_ok());
+        assert_eq!(
+            check_oracle_standing(Some(&oracle_with_stake(999)), &config).unwrap_err(),
+            ErrorCode::InsufficientOracleStake.into()
+        );
+    }
+    
+    #[test]
+    fn oracle_standing_rejects_a_revoked_oracle() {
+        let mut registered = oracle_with_stake(1_000);
+        registered.is_active = false;
+        assert_eq!(
+            check_oracle_standing(Some(&registered), &staked_config(0)).unwrap_err(),
+            ErrorCode::OracleNotRegistered.into()
+        );
+    }
+    
+    #[test]
+    fn oracle_standing_refuses_unregistered_verifiers_once_staking_is_on() {
+        assert!(check_oracle_standing(None, &staked_config(0)).is_ok());
+        assert_eq!(
+            check_oracle_standing(None, &staked_config(1)).unwrap_err(),
+            ErrorCode::OracleNotRegistered.into()
+        );
+    }
+    
+    #[test]
+    fn weakest_status_ranks_non_compliant_lowest() {
+        use ComplianceStatus::*;
+        assert!(Compliant.weakest(PendingReview) == PendingReview);
+        assert!(PendingReview.weakest(Compliant) == PendingReview);
+        assert!(PendingReview.weakest(NonCompliant) == NonCompliant);
+        assert!(NonCompliant.weakest(Compliant) == NonCompliant);
+    }
+    
+    #[test]
+    fn weakest_status_of_equals_is_itself() {
+        use ComplianceStatus::*;
+        for status in [Compliant, PendingReview, NonCompliant] {
+            assert!(status.weakest(status) == status);
+        }
+    }
+    
+    #[test]
+    fn default_policy_requires_a_registered_oracle() {
+        let policy = CommodityPolicy::default_for(CommodityType::Coffee);
+        assert_eq!(
+            policy.check_verifier_tier(None).unwrap_err(),
+            ErrorCode::InsufficientVerifierTier.into()
+        );
+        assert!(policy.check_verifier_tier(Some(&oracle_with_stake(0))).is_err());
+        let mut registered = oracle_with_stake(0);
+        registered.tier = 1;
+        assert!(policy.check_verifier_tier(Some(&registered)).is_ok());
+    }
+    
+    #[test]
+    fn weights_normalize_to_kilograms() {
+        assert_eq!(WeightUnit::Kilogram.to_kg(750).unwrap(), 750);
+        assert_eq!(WeightUnit::Tonne.to_kg(3).unwrap(), 3_000);
+        assert_eq!(WeightUnit::Gram.to_kg(2_000).unwrap(), 2);
+    }
+    
+    #[test]
+    fn grams_round_to_the_nearest_kilogram() {
+        assert_eq!(WeightUnit::Gram.to_kg(1_499).unwrap(), 1);
+        assert_eq!(WeightUnit::Gram.to_kg(1_500).unwrap(), 2);
+    }
+    
+    #[test]
+    fn weights_under_half_a_kilogram_are_rejected() {
+        assert_eq!(WeightUnit::Gram.to_kg(499).unwrap_err(), ErrorCode::InvalidWeight.into());
+        assert_eq!(WeightUnit::Kilogram.to_kg(0).unwrap_err(), ErrorCode::InvalidWeight.into());
+    }
+    
+    #[test]
+    fn tonnes_past_u64_overflow() {
+        assert_eq!(WeightUnit::Tonne.to_kg(u64::MAX / 1_000 + 1).unwrap_err(), ErrorCode::WeightOverflow.into());
+    }
+    
+    fn bbox(min_lat_e6: i32, min_lon_e6: i32, max_lat_e6: i32, max_lon_e6: i32) -> BoundingBox {
+        BoundingBox { min_lat_e6, min_lon_e6, max_lat_e6, max_lon_e6 }
+    }
+    
+    #[test]
+    fn polygon_bounding_box_spans_its_vertices() {
+        let bounding_box = plot_bounding_box("[[5.10,-1.20],[5.10,-1.19],[5.11,-1.19],[5.11,-1.20]]").unwrap();
+        assert!(bounding_box == bbox(5_100_000, -1_200_000, 5_110_000, -1_190_000));
+    }
+    
+    #[test]
+    fn point_radius_bounding_box_covers_the_circle() {
+        let bounding_box = plot_bounding_box("point:0,0,11132").unwrap();
+        assert!(bounding_box == bbox(-100_000, -100_000, 100_000, 100_000));
+    }
+    
+    #[test]
+    fn hash_only_coordinates_have_no_bounding_box() {
+        assert!(plot_bounding_box("sha256:9f86d081884c7d659a2feaa0c55ad015").is_none());
+    }
+    
+    #[test]
+    fn overlapping_boxes_are_detected() {
+        let a = bbox(0, 0, 1_000, 1_000);
+        assert!(check_plot_overlap(&a, &bbox(500, 500, 1_500, 1_500)));
+        assert!(check_plot_overlap(&a, &bbox(200, 200, 300, 300)));
+    }
+    
+    #[test]
+    fn disjoint_and_touching_boxes_do_not_overlap() {
+        let a = bbox(0, 0, 1_000, 1_000);
+        assert!(!check_plot_overlap(&a, &bbox(2_000, 2_000, 3_000, 3_000)));
+        // Neighbors sharing a boundary overlap only within GPS tolerance
+        assert!(!check_plot_overlap(&a, &bbox(1_000 - OVERLAP_TOLERANCE_E6 as i32, 0, 2_000, 1_000)));
+        assert!(check_plot_overlap(&a, &bbox(1_000 - OVERLAP_TOLERANCE_E6 as i32 - 1, 0, 2_000, 1_000)));
+    }
+}
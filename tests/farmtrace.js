@@ -1,13 +1,921 @@
+const crypto = require("crypto");
 const anchor = require("@coral-xyz/anchor");
+const { assert } = require("chai");
+
+const { BN } = anchor;
+const { Ed25519Program, Keypair, PublicKey, LAMPORTS_PER_SOL, SYSVAR_INSTRUCTIONS_PUBKEY } = anchor.web3;
 
 describe("farmtrace", () => {
   // Configure the client to use the local cluster.
-  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.farmtrace;
+  const admin = provider.wallet;
+
+  const COMMODITIES = ["cocoa", "coffee", "palmOil", "soy", "cattle", "rubber", "timber", "other"];
+  const POLYGON = "[[5.10,-1.20],[5.10,-1.19],[5.11,-1.19],[5.11,-1.20]]";
+
+  // ---------------------------------------------------------------------------
+  // Helpers
+  // ---------------------------------------------------------------------------
+
+  const toSeed = (seed) => {
+    if (typeof seed === "string") return Buffer.from(seed);
+    if (seed instanceof PublicKey) return seed.toBuffer();
+    return Buffer.from(seed);
+  };
+  const pda = (...seeds) =>
+    PublicKey.findProgramAddressSync(seeds.map(toSeed), program.programId)[0];
+
+  const configPda = () => pda("config");
+  const plotPda = (plotId, farmer) => pda("farm_plot", plotId, farmer);
+  const profilePda = (farmer) => pda("farmer", farmer);
+  const auditPda = (plot) => pda("audit", plot);
+  const policyPda = (commodity) => pda("policy", [COMMODITIES.indexOf(commodity)]);
+
+  let nextId = 0;
+  const uniqueId = (prefix) => `${prefix}-${Date.now() % 100000}-${nextId++}`;
+
+  const now = () => Math.floor(Date.now() / 1000);
+
+  async function fundedKeypair(sol = 10) {
+    const keypair = Keypair.generate();
+    const signature = await provider.connection.requestAirdrop(keypair.publicKey, sol * LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(signature, "confirmed");
+    return keypair;
+  }
+
+  // Asserts the promise fails with the named program error
+  async function expectError(promise, code) {
+    try {
+      await promise;
+    } catch (err) {
+      const actual = (err.error && err.error.errorCode && err.error.errorCode.code) || err.toString();
+      assert.include(String(actual), code);
+      return;
+    }
+    assert.fail(`expected ${code}`);
+  }
+
+  async function registerPlot(farmer, options = {}) {
+    const plotId = options.plotId || uniqueId("plot");
+    const commodity = options.commodity || "cocoa";
+    const farmPlot = plotPda(plotId, farmer.publicKey);
+    await program.methods
+      .registerFarmPlot(
+        plotId,
+        "Ama Mensah",
+        "Ashanti",
+        Array.from(Buffer.from("GH")),
+        options.coordinates || POLYGON,
+        options.areaHectares || 2.5,
+        { [commodity]: {} },
+        "",
+        new anchor.BN(options.registrationTimestamp || now() - 400 * 86400),
+        null,
+        options.harvestSeason || null
+      )
+      .accountsPartial({
+        farmPlot,
+        farmerProfile: profilePda(farmer.publicKey),
+        auditLog: auditPda(farmPlot),
+        approvedDevice: null,
+        instructionsSysvar: null,
+        commodityPolicy: policyPda(commodity),
+        cosigner: null,
+        approvedCosigner: null,
+        config: configPda(),
+        farmer: farmer.publicKey,
+      })
+      .signers([farmer])
+      .rpc();
+    return { plotId, farmPlot };
+  }
+
+  const commodityOf = (plot) => Object.keys(plot.commodityType)[0];
+  const randomHash = () => crypto.randomBytes(32).toString("hex");
+  const imagerySeed = (hash) => crypto.createHash("sha256").update(hash).digest();
+  const indexSegment = (verificationCount) => {
+    const segment = Buffer.alloc(4);
+    segment.writeUInt32LE(Math.floor(verificationCount / 32));
+    return segment;
+  };
+
+  async function setPolicy(commodity, overrides = {}) {
+    const params = {
+      minComplianceScore: 70,
+      verificationValiditySeconds: 0,
+      minVerificationIntervalSeconds: 0,
+      maxYieldKgPerHectare: 0,
+      requiresCosigner: false,
+      minAgeBeforeHarvestSeconds: 0,
+      smallholderMinAgeBeforeHarvestSeconds: 0,
+      allowProvisionalMediumRisk: false,
+      verifierTierRequired: 0,
+      ...overrides,
+    };
+    await program.methods
+      .setCommodityPolicy(
+        { [commodity]: {} },
+        params.minComplianceScore,
+        new BN(params.verificationValiditySeconds),
+        new BN(params.minVerificationIntervalSeconds),
+        new BN(params.maxYieldKgPerHectare),
+        params.requiresCosigner,
+        new BN(params.minAgeBeforeHarvestSeconds),
+        new BN(params.smallholderMinAgeBeforeHarvestSeconds),
+        params.allowProvisionalMediumRisk,
+        params.verifierTierRequired
+      )
+      .accountsPartial({ commodityPolicy: policyPda(commodity), config: configPda(), admin: admin.publicKey })
+      .rpc();
+  }
+
+  async function approvedOracle(tier = 1) {
+    const oracle = await fundedKeypair();
+    await program.methods
+      .approveOracle(oracle.publicKey, tier)
+      .accountsPartial({ registeredOracle: pda("oracle", oracle.publicKey), config: configPda(), admin: admin.publicKey })
+      .rpc();
+    return oracle;
+  }
+
+  // Nonces must increase per oracle; a failed submission just skips one
+  const oracleNonces = new Map();
+
+  // Builds the Ed25519 instruction over an OracleMessage's Borsh bytes and the
+  // SignedOracleMessage argument that must match it
+  function signOracleMessage(oracle, farmPlot, noDeforestation, confidence, extras = {}) {
+    const challengeNonce = extras.challengeNonce || Buffer.alloc(32);
+    const canopyCoverBps = extras.canopyCoverBps === undefined ? null : extras.canopyCoverBps;
+    const nonce = (oracleNonces.get(oracle.publicKey.toBase58()) || 0) + 1;
+    oracleNonces.set(oracle.publicKey.toBase58(), nonce);
+    const expiresAt = now() + 600;
+    const bytes = Buffer.alloc(canopyCoverBps === null ? 83 : 85);
+    farmPlot.toBuffer().copy(bytes, 0);
+    bytes.writeUInt8(noDeforestation ? 1 : 0, 32);
+    bytes.writeUInt8(confidence, 33);
+    bytes.writeBigUInt64LE(BigInt(nonce), 34);
+    bytes.writeBigInt64LE(BigInt(expiresAt), 42);
+    Buffer.from(challengeNonce).copy(bytes, 50);
+    if (canopyCoverBps !== null) {
+      bytes.writeUInt8(1, 82);
+      bytes.writeUInt16LE(canopyCoverBps, 83);
+    }
+    const ed25519Ix = Ed25519Program.createInstructionWithPrivateKey({
+      privateKey: oracle.secretKey,
+      message: bytes,
+    });
+    return {
+      ed25519Ix,
+      signed: {
+        message: {
+          plot: farmPlot,
+          result: noDeforestation,
+          confidence,
+          nonce: new BN(nonce),
+          expiresAt: new BN(expiresAt),
+          challengeNonce: Array.from(challengeNonce),
+          canopyCoverBps,
+        },
+        // Ed25519 instruction layout: 16-byte header, pubkey, then the signature
+        signature: Array.from(ed25519Ix.data.subarray(48, 112)),
+      },
+    };
+  }
+
+  async function recordVerification(oracle, farmPlot, options = {}) {
+    const noDeforestation = options.noDeforestation !== undefined ? options.noDeforestation : true;
+    const confidence = options.confidence || 0;
+    const hash = options.hash || randomHash();
+    const plot = await program.account.farmPlot.fetch(farmPlot);
+    const { ed25519Ix, signed } = signOracleMessage(oracle, farmPlot, noDeforestation, confidence, options);
+    const verification = pda("verification", farmPlot, oracle.publicKey, imagerySeed(hash));
+    await program.methods
+      .recordSatelliteVerification(
+        hash,
+        noDeforestation,
+        new BN(options.verificationTimestamp || now()),
+        confidence,
+        new BN(0),
+        "sentinel-2",
+        new BN(now() - 86400),
+        signed
+      )
+      .accountsPartial({
+        verification,
+        farmPlot,
+        farmerProfile: profilePda(plot.farmer),
+        auditLog: auditPda(farmPlot),
+        verificationIndex: pda("verification_index", farmPlot, indexSegment(plot.verificationCount)),
+        commodityPolicy: policyPda(commodityOf(plot)),
+        challenge: options.challenge ? pda("challenge", farmPlot, oracle.publicKey) : null,
+        config: configPda(),
+        registeredOracle: options.unregistered ? null : pda("oracle", oracle.publicKey),
+        oracleNonce: pda("oracle_nonce", oracle.publicKey),
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        fundingPool: null,
+        verifier: oracle.publicKey,
+      })
+      .preInstructions([ed25519Ix])
+      .signers([oracle])
+      .rpc();
+    return verification;
+  }
+
+  async function recordBaseline(authority, farmPlot, canopyCoverBps, options = {}) {
+    await program.methods
+      .recordLandCoverBaseline(canopyCoverBps, randomHash(), new BN(now() - 86400))
+      .accountsPartial({
+        baseline: pda("baseline", farmPlot),
+        farmPlot,
+        auditLog: auditPda(farmPlot),
+        registeredOracle: options.asOracle ? pda("oracle", authority.publicKey) : null,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  async function issueChallenge(authority, farmPlot, verifier) {
+    const challenge = pda("challenge", farmPlot, verifier);
+    await program.methods
+      .issueVerificationChallenge(verifier)
+      .accountsPartial({ challenge, farmPlot, config: configPda(), authority: authority.publicKey })
+      .signers(authority.payer ? [] : [authority])
+      .rpc();
+    return program.account.verificationChallenge.fetch(challenge);
+  }
+
+  // Registers a plot and records a clean oracle reading, so it can ship batches
+  async function verifiedPlot(farmer, oracle) {
+    const registered = await registerPlot(farmer);
+    await recordVerification(oracle, registered.farmPlot, { confidence: 100 });
+    return registered;
+  }
+
+  const batchPda = (batchId, farmer) => pda("harvest_batch", batchId, farmer);
+  const ddsPda = (batch) => pda("dds", batch);
+
+  async function registerBatch(farmer, farmPlot, batchId) {
+    const harvestBatch = batchPda(batchId, farmer.publicKey);
+    await program.methods
+      .registerHarvestBatch(batchId, new BN(500), { kilogram: {} }, new BN(now() - 3600), null, [], null)
+      .accountsPartial({
+        harvestBatch,
+        priorDdsRecord: ddsPda(harvestBatch),
+        farmPlot,
+        farmerProfile: profilePda(farmer.publicKey),
+        auditLog: auditPda(farmPlot),
+        crop: null,
+        commodityPolicy: policyPda(commodityOf(await program.account.farmPlot.fetch(farmPlot))),
+        config: configPda(),
+        backfillAdmin: null,
+        farmer: farmer.publicKey,
+      })
+      .signers([farmer])
+      .rpc();
+    return harvestBatch;
+  }
+
+  async function recordDds(farmer, harvestBatch, farmPlot) {
+    await program.methods
+      .recordDds()
+      .accountsPartial({
+        ddsRecord: ddsPda(harvestBatch),
+        harvestBatch,
+        farmPlot,
+        processingRecord: null,
+        verification: null,
+        payment: null,
+        farmer: farmer.publicKey,
+      })
+      .signers([farmer])
+      .rpc();
+  }
+
+  async function setBatchStatus(authority, harvestBatch, status) {
+    const batch = await program.account.harvestBatch.fetch(harvestBatch);
+    const seq = Buffer.alloc(8);
+    seq.writeBigUInt64LE(BigInt(batch.updateSeq.toString()));
+    await program.methods
+      .updateBatchStatus({ [status]: {} }, "Rotterdam", null)
+      .accountsPartial({
+        harvestBatch,
+        statusUpdate: pda("batch_update", harvestBatch, seq),
+        config: configPda(),
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  async function closeBatch(farmer, harvestBatch, ddsRecord) {
+    await program.methods
+      .closeBatch()
+      .accountsPartial({ harvestBatch, ddsRecord, farmer: farmer.publicKey })
+      .signers([farmer])
+      .rpc();
+  }
+
+  before(async () => {
+    const existing = await provider.connection.getAccountInfo(configPda());
+    if (!existing) {
+      await program.methods
+        .initializeConfig()
+        .accountsPartial({ config: configPda(), admin: admin.publicKey })
+        .rpc();
+    }
+  });
+
+  // ---------------------------------------------------------------------------
+  // Config and registration
+  // ---------------------------------------------------------------------------
+
+  it("initializes the config with the signer as admin", async () => {
+    const config = await program.account.programConfig.fetch(configPda());
+    assert.ok(config.admin.equals(admin.publicKey));
+    assert.isFalse(config.paused);
+  });
+
+  it("registers a plot with an in-range compliance score", async () => {
+    const farmer = await fundedKeypair();
+    const { farmPlot } = await registerPlot(farmer);
+    const plot = await program.account.farmPlot.fetch(farmPlot);
+    assert.isAtLeast(plot.complianceScore, 0);
+    assert.isAtMost(plot.complianceScore, 100);
+  });
+
+  // ---------------------------------------------------------------------------
+  // Verification cadence (synth-316)
+  // ---------------------------------------------------------------------------
+
+  describe("re-verification cooldown", () => {
+    let oracle;
+
+    before(async () => {
+      await setPolicy("rubber", { minVerificationIntervalSeconds: 86400 });
+      oracle = await approvedOracle();
+    });
+
+    it("rejects a second verification inside the cooldown", async () => {
+      const farmer = await fundedKeypair();
+      const { farmPlot } = await registerPlot(farmer, { commodity: "rubber" });
+      await recordVerification(oracle, farmPlot);
+      await expectError(recordVerification(oracle, farmPlot), "VerificationTooSoon");
+    });
+
+    it("rejects a forward-dated verification timestamp", async () => {
+      const farmer = await fundedKeypair();
+      const { farmPlot } = await registerPlot(farmer, { commodity: "rubber" });
+      await expectError(
+        recordVerification(oracle, farmPlot, { verificationTimestamp: now() + 30 * 86400 }),
+        "VerificationInFuture"
+      );
+    });
+
+    it("runs expiry from the cluster clock, not the oracle's timestamp", async () => {
+      const farmer = await fundedKeypair();
+      const { farmPlot } = await registerPlot(farmer, { commodity: "rubber" });
+      await recordVerification(oracle, farmPlot, { verificationTimestamp: now() - 10 * 86400 });
+      const plot = await program.account.farmPlot.fetch(farmPlot);
+      assert.approximately(plot.lastVerified.toNumber(), now(), 120);
+      assert.equal(plot.verificationExpiresAt.toNumber(), plot.lastVerified.toNumber() + 365 * 86400);
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Challenge-response (synth-277)
+  // ---------------------------------------------------------------------------
+
+  describe("verification challenges", () => {
+    let oracle;
+    let farmer;
+
+    before(async () => {
+      oracle = await approvedOracle();
+      farmer = await fundedKeypair();
+    });
+
+    it("accepts a verification that signs the issued nonce", async () => {
+      const { farmPlot } = await registerPlot(farmer);
+      const challenge = await issueChallenge(farmer, farmPlot, oracle.publicKey);
+      await recordVerification(oracle, farmPlot, { challenge: true, challengeNonce: challenge.nonce });
+      const answered = await program.account.verificationChallenge.fetch(pda("challenge", farmPlot, oracle.publicKey));
+      assert.isTrue(answered.consumed);
+    });
+
+    it("rejects a replayed challenge", async () => {
+      const { farmPlot } = await registerPlot(farmer);
+      const challenge = await issueChallenge(farmer, farmPlot, oracle.publicKey);
+      await recordVerification(oracle, farmPlot, { challenge: true, challengeNonce: challenge.nonce });
+      await expectError(
+        recordVerification(oracle, farmPlot, { challenge: true, challengeNonce: challenge.nonce }),
+        "ChallengeAlreadyUsed"
+      );
+    });
+
+    it("rejects a nonce that doesn't match the challenge", async () => {
+      const { farmPlot } = await registerPlot(farmer);
+      await issueChallenge(farmer, farmPlot, oracle.publicKey);
+      await expectError(
+        recordVerification(oracle, farmPlot, { challenge: true, challengeNonce: crypto.randomBytes(32) }),
+        "ChallengeMismatch"
+      );
+    });
+
+    it("doesn't let the oracle issue its own challenge", async () => {
+      const { farmPlot } = await registerPlot(farmer);
+      await expectError(issueChallenge(oracle, farmPlot, oracle.publicKey), "Unauthorized");
+    });
+
+    it("requires a challenge once the config demands one", async () => {
+      const { farmPlot } = await registerPlot(farmer);
+      const setRequired = (required) =>
+        program.methods.setChallengeRequired(required).accountsPartial({ config: configPda(), admin: admin.publicKey }).rpc();
+      await setRequired(true);
+      try {
+        await expectError(recordVerification(oracle, farmPlot), "ChallengeRequired");
+      } finally {
+        await setRequired(false);
+      }
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Land-cover baseline (synth-278~2)
+  // ---------------------------------------------------------------------------
+
+  describe("land-cover baseline", () => {
+    let oracle;
+    let farmer;
+
+    before(async () => {
+      oracle = await approvedOracle();
+      farmer = await fundedKeypair();
+    });
+
+    it("lets the farmer record a baseline the plot references", async () => {
+      const { farmPlot } = await registerPlot(farmer);
+      await recordBaseline(farmer, farmPlot, 8000);
+      const plot = await program.account.farmPlot.fetch(farmPlot);
+      assert.ok(plot.landCoverBaseline.equals(pda("baseline", farmPlot)));
+      assert.equal(plot.baselineCanopyCoverBps, 8000);
+    });
+
+    it("lets a registered oracle record a baseline", async () => {
+      const { farmPlot } = await registerPlot(farmer);
+      await recordBaseline(oracle, farmPlot, 7000, { asOracle: true });
+      const baseline = await program.account.landCoverBaseline.fetch(pda("baseline", farmPlot));
+      assert.ok(baseline.recordedBy.equals(oracle.publicKey));
+    });
+
+    it("rejects a baseline from anyone else", async () => {
+      const { farmPlot } = await registerPlot(farmer);
+      const stranger = await fundedKeypair();
+      await expectError(recordBaseline(stranger, farmPlot, 9000), "Unauthorized");
+    });
+
+    it("measures a later verification against the baseline", async () => {
+      const { farmPlot } = await registerPlot(farmer);
+      await recordBaseline(farmer, farmPlot, 8000);
+      const verification = await recordVerification(oracle, farmPlot, { canopyCoverBps: 7600 });
+      const recorded = await program.account.satelliteVerification.fetch(verification);
+      assert.ok(recorded.baseline.equals(pda("baseline", farmPlot)));
+      assert.equal(recorded.canopyChangeBps, -400);
+    });
+
+    it("rejects a clean result that reports canopy loss past tolerance", async () => {
+      const { farmPlot } = await registerPlot(farmer);
+      await recordBaseline(farmer, farmPlot, 8000);
+      await expectError(recordVerification(oracle, farmPlot, { canopyCoverBps: 5000 }), "CanopyLossAgainstBaseline");
+    });
+
+    it("requires a canopy reading once the plot has a baseline", async () => {
+      const { farmPlot } = await registerPlot(farmer);
+      await recordBaseline(farmer, farmPlot, 8000);
+      await expectError(recordVerification(oracle, farmPlot), "CanopyReadingRequired");
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Metadata hash (synth-298)
+  // ---------------------------------------------------------------------------
+
+  describe("metadata hash", () => {
+    const lengthPrefixed = (value) => {
+      const bytes = Buffer.from(value);
+      const length = Buffer.alloc(4);
+      length.writeUInt32LE(bytes.length);
+      return Buffer.concat([length, bytes]);
+    };
+
+    // Mirrors plot_metadata_hash so off-chain copies can be checked
+    const metadataHash = (plot) => {
+      const area = Buffer.alloc(8);
+      area.writeDoubleLE(plot.areaHectares);
+      const registered = Buffer.alloc(8);
+      registered.writeBigInt64LE(BigInt(plot.registrationTimestamp.toString()));
+      return crypto
+        .createHash("sha256")
+        .update(
+          Buffer.concat([
+            lengthPrefixed(plot.plotId),
+            plot.farmer.toBuffer(),
+            lengthPrefixed(plot.farmerName),
+            lengthPrefixed(plot.location),
+            lengthPrefixed(plot.coordinates),
+            area,
+            Buffer.from([COMMODITIES.indexOf(commodityOf(plot))]),
+            lengthPrefixed(plot.commodityLabel),
+            registered,
+          ])
+        )
+        .digest();
+    };
+
+    it("stores the canonical hash of the plot at registration", async () => {
+      const farmer = await fundedKeypair();
+      const { farmPlot } = await registerPlot(farmer);
+      const plot = await program.account.farmPlot.fetch(farmPlot);
+      assert.deepEqual(Buffer.from(plot.metadataHash), metadataHash(plot));
+      await program.methods.verifyMetadataHash(plot.metadataHash).accountsPartial({ farmPlot }).rpc();
+    });
+
+    it("rejects a hash computed over modified plot data", async () => {
+      const farmer = await fundedKeypair();
+      const { farmPlot } = await registerPlot(farmer);
+      const plot = await program.account.farmPlot.fetch(farmPlot);
+      const tampered = metadataHash({ ...plot, areaHectares: plot.areaHectares * 10 });
+      await expectError(
+        program.methods.verifyMetadataHash(Array.from(tampered)).accountsPartial({ farmPlot }).rpc(),
+        "MetadataHashMismatch"
+      );
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // DDS binding (synth-286)
+  // ---------------------------------------------------------------------------
+
+  describe("DDS binding", () => {
+    let oracle;
+    let farmer;
+
+    before(async () => {
+      oracle = await approvedOracle();
+      farmer = await fundedKeypair();
+    });
+
+    it("requires the batch's own DDS before closing", async () => {
+      const { farmPlot } = await verifiedPlot(farmer, oracle);
+      const harvestBatch = await registerBatch(farmer, farmPlot, uniqueId("batch"));
+      await setBatchStatus(farmer, harvestBatch, "delivered");
+      await expectError(closeBatch(farmer, harvestBatch, null), "DDSRequiredBeforeClose");
+      await recordDds(farmer, harvestBatch, farmPlot);
+      await closeBatch(farmer, harvestBatch, ddsPda(harvestBatch));
+      assert.isNull(await provider.connection.getAccountInfo(harvestBatch));
+    });
+
+    it("won't accept another batch's DDS", async () => {
+      const { farmPlot } = await verifiedPlot(farmer, oracle);
+      const other = await registerBatch(farmer, farmPlot, uniqueId("batch"));
+      await recordDds(farmer, other, farmPlot);
+      const harvestBatch = await registerBatch(farmer, farmPlot, uniqueId("batch"));
+      await setBatchStatus(farmer, harvestBatch, "delivered");
+      await expectError(closeBatch(farmer, harvestBatch, ddsPda(other)), "ConstraintSeeds");
+    });
+
+    it("refuses to reuse a batch ID whose DDS outlived the batch", async () => {
+      const { farmPlot } = await verifiedPlot(farmer, oracle);
+      const batchId = uniqueId("batch");
+      const harvestBatch = await registerBatch(farmer, farmPlot, batchId);
+      await recordDds(farmer, harvestBatch, farmPlot);
+      await setBatchStatus(farmer, harvestBatch, "delivered");
+      await closeBatch(farmer, harvestBatch, ddsPda(harvestBatch));
+      await expectError(registerBatch(farmer, farmPlot, batchId), "BatchIdRetired");
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Oracle stake (synth-338)
+  // ---------------------------------------------------------------------------
+
+  describe("oracle stake", () => {
+    const setStakeRequirement = (lamports) =>
+      program.methods
+        .setOracleStakeRequirement(new BN(lamports))
+        .accountsPartial({ config: configPda(), admin: admin.publicKey })
+        .rpc();
+
+    it("refuses verifications from under-staked or unregistered oracles while staking is on", async () => {
+      const oracle = await approvedOracle();
+      const farmer = await fundedKeypair();
+      const { farmPlot } = await registerPlot(farmer);
+      await setStakeRequirement(LAMPORTS_PER_SOL);
+      try {
+        await expectError(recordVerification(oracle, farmPlot), "InsufficientOracleStake");
+        await expectError(recordVerification(oracle, farmPlot, { unregistered: true }), "OracleNotRegistered");
+      } finally {
+        await setStakeRequirement(0);
+      }
+      await recordVerification(oracle, farmPlot);
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Coordinate revisions (synth-340)
+  // ---------------------------------------------------------------------------
+
+  describe("coordinate revisions", () => {
+    const NEARBY = "[[5.20,-1.30],[5.20,-1.29],[5.21,-1.29],[5.21,-1.30]]";
+    const OVERLAPPING = "[[5.205,-1.305],[5.205,-1.295],[5.215,-1.295],[5.215,-1.305]]";
+    const DISTANT = "[[6.20,-2.30],[6.20,-2.29],[6.21,-2.29],[6.21,-2.30]]";
+
+    async function reviseCoordinates(farmer, farmPlot, coordinates, neighbors = []) {
+      const plot = await program.account.farmPlot.fetch(farmPlot);
+      const revision = Buffer.alloc(2);
+      revision.writeUInt16LE(plot.coordinateRevision);
+      await program.methods
+        .updatePlotCoordinates(coordinates, "resurveyed boundary")
+        .accountsPartial({
+          farmPlot,
+          coordinateHistory: pda("coord_history", farmPlot, revision),
+          auditLog: auditPda(farmPlot),
+          config: configPda(),
+          farmer: farmer.publicKey,
+        })
+        .remainingAccounts(neighbors.map((pubkey) => ({ pubkey, isWritable: false, isSigner: false })))
+        .signers([farmer])
+        .rpc();
+    }
+
+    it("derives the bounding box from the revised geometry", async () => {
+      const farmer = await fundedKeypair();
+      const { farmPlot } = await registerPlot(farmer);
+      await reviseCoordinates(farmer, farmPlot, NEARBY);
+      const plot = await program.account.farmPlot.fetch(farmPlot);
+      assert.equal(plot.boundingBox.minLatE6, 5200000);
+      assert.equal(plot.boundingBox.maxLonE6, -1290000);
+    });
+
+    it("rejects a revision that overlaps a neighbor", async () => {
+      const farmer = await fundedKeypair();
+      const neighbor = await registerPlot(farmer);
+      await reviseCoordinates(farmer, neighbor.farmPlot, NEARBY);
+      const { farmPlot } = await registerPlot(farmer, { coordinates: DISTANT });
+      await expectError(reviseCoordinates(farmer, farmPlot, OVERLAPPING, [neighbor.farmPlot]), "PlotOverlap");
+    });
+
+    it("doesn't check a plot against its own old boundary", async () => {
+      const farmer = await fundedKeypair();
+      const { farmPlot } = await registerPlot(farmer);
+      await reviseCoordinates(farmer, farmPlot, NEARBY);
+      await reviseCoordinates(farmer, farmPlot, OVERLAPPING, [farmPlot]);
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Recalls (synth-293)
+  // ---------------------------------------------------------------------------
+
+  describe("recalls", () => {
+    let oracle;
+    let farmer;
+
+    before(async () => {
+      oracle = await approvedOracle();
+      farmer = await fundedKeypair();
+    });
+
+    const recallAccounts = (harvestBatch, authority) => ({
+      harvestBatch,
+      config: configPda(),
+      authority: authority.publicKey,
+    });
+    const requestRecall = (harvestBatch, authority) =>
+      program.methods
+        .recallBatch("buyer rejected shipment")
+        .accountsPartial(recallAccounts(harvestBatch, authority))
+        .signers(authority.payer ? [] : [authority])
+        .rpc();
+    const resolveRecall = (harvestBatch, authority, finalize) =>
+      program.methods
+        .resolveRecall(finalize)
+        .accountsPartial(recallAccounts(harvestBatch, authority))
+        .signers(authority.payer ? [] : [authority])
+        .rpc();
+
+    async function soldBatch() {
+      const { farmPlot } = await verifiedPlot(farmer, oracle);
+      const harvestBatch = await registerBatch(farmer, farmPlot, uniqueId("batch"));
+      const buyer = await fundedKeypair();
+      await program.methods
+        .transferBatchOwnership(buyer.publicKey)
+        .accountsPartial({ harvestBatch, owner: farmer.publicKey })
+        .signers([farmer])
+        .rpc();
+      return { harvestBatch, buyer };
+    }
+
+    it("lets the holder request a recall and resolve it, but not the farmer", async () => {
+      const { harvestBatch, buyer } = await soldBatch();
+      await requestRecall(harvestBatch, buyer);
+      await expectError(resolveRecall(harvestBatch, farmer, false), "Unauthorized");
+      await resolveRecall(harvestBatch, buyer, false);
+      const batch = await program.account.harvestBatch.fetch(harvestBatch);
+      assert.deepEqual(batch.status, { harvested: {} });
+      assert.isNull(batch.recallRequestedBy);
+    });
+
+    it("leaves a farmer's own recall to the admin", async () => {
+      const { harvestBatch } = await soldBatch();
+      await requestRecall(harvestBatch, farmer);
+      await expectError(resolveRecall(harvestBatch, farmer, true), "Unauthorized");
+      await resolveRecall(harvestBatch, admin, true);
+      const batch = await program.account.harvestBatch.fetch(harvestBatch);
+      assert.deepEqual(batch.status, { recalled: {} });
+    });
+
+    it("refuses a recall from anyone else", async () => {
+      const { harvestBatch } = await soldBatch();
+      const stranger = await fundedKeypair();
+      await expectError(requestRecall(harvestBatch, stranger), "Unauthorized");
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Processing steps (synth-276)
+  // ---------------------------------------------------------------------------
+
+  describe("processing steps", () => {
+    let oracle;
+    let farmer;
+
+    before(async () => {
+      oracle = await approvedOracle();
+      farmer = await fundedKeypair();
+    });
+
+    const stepSeed = (step) => {
+      const seed = Buffer.alloc(4);
+      seed.writeUInt32LE(step);
+      return seed;
+    };
+
+    async function recordProcessing(harvestBatch, outputWeightKg, processingType) {
+      const batch = await program.account.harvestBatch.fetch(harvestBatch);
+      const processingRecord = pda("processing", harvestBatch, stepSeed(batch.processingCount));
+      await program.methods
+        .recordProcessing(new BN(outputWeightKg), processingType)
+        .accountsPartial({ processingRecord, harvestBatch, authority: farmer.publicKey })
+        .signers([farmer])
+        .rpc();
+      return program.account.processingRecord.fetch(processingRecord);
+    }
+
+    it("chains several steps, each starting from the last one's output", async () => {
+      const { farmPlot } = await verifiedPlot(farmer, oracle);
+      const harvestBatch = await registerBatch(farmer, farmPlot, uniqueId("batch"));
+      const drying = await recordProcessing(harvestBatch, 400, "sun drying on raised beds");
+      const fermenting = await recordProcessing(harvestBatch, 300, "heap fermentation");
+      assert.equal(drying.step, 0);
+      assert.equal(fermenting.step, 1);
+      assert.equal(fermenting.inputWeightKg.toNumber(), 400);
+      const batch = await program.account.harvestBatch.fetch(harvestBatch);
+      assert.equal(batch.processingCount, 2);
+      assert.equal(batch.traceableWeightKg.toNumber(), 300);
+    });
+
+    it("allows descriptive processing types up to the dedicated limit", async () => {
+      const { farmPlot } = await verifiedPlot(farmer, oracle);
+      const harvestBatch = await registerBatch(farmer, farmPlot, uniqueId("batch"));
+      await recordProcessing(harvestBatch, 400, "x".repeat(48));
+      await expectError(recordProcessing(harvestBatch, 300, "x".repeat(49)), "ProcessingTypeTooLong");
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Storage lot withdrawals (synth-276~2)
+  // ---------------------------------------------------------------------------
+
+  describe("storage lot withdrawals", () => {
+    let oracle;
+    let farmer;
+
+    before(async () => {
+      oracle = await approvedOracle();
+      farmer = await fundedKeypair();
+    });
+
+    const withdrawalPda = (storageLot, index) => {
+      const seed = Buffer.alloc(4);
+      seed.writeUInt32LE(index);
+      return pda("lot_withdrawal", storageLot, seed);
+    };
+
+    async function filledLot() {
+      const lotId = uniqueId("lot");
+      const storageLot = pda("storage_lot", lotId, farmer.publicKey);
+      await program.methods
+        .createStorageLot(lotId, { cocoa: {} })
+        .accountsPartial({ storageLot, operator: farmer.publicKey })
+        .signers([farmer])
+        .rpc();
+      const { farmPlot } = await verifiedPlot(farmer, oracle);
+      const harvestBatch = await registerBatch(farmer, farmPlot, uniqueId("batch"));
+      await program.methods
+        .depositToLot()
+        .accountsPartial({
+          storageLot,
+          lotDeposit: pda("lot_deposit", storageLot, harvestBatch),
+          harvestBatch,
+          authority: farmer.publicKey,
+        })
+        .signers([farmer])
+        .rpc();
+      return storageLot;
+    }
+
+    async function withdraw(storageLot, weightKg, destination) {
+      const lot = await program.account.storageLot.fetch(storageLot);
+      const withdrawal = withdrawalPda(storageLot, lot.withdrawalCount);
+      await program.methods
+        .withdrawFromLot(new BN(weightKg), destination)
+        .accountsPartial({ storageLot, withdrawal, config: configPda(), operator: farmer.publicKey })
+        .signers([farmer])
+        .rpc();
+      return program.account.lotWithdrawal.fetch(withdrawal);
+    }
+
+    it("keeps a record of every withdrawal", async () => {
+      const storageLot = await filledLot();
+      const first = await withdraw(storageLot, 200, "Antwerp");
+      const second = await withdraw(storageLot, 200, "Hamburg");
+      assert.equal(first.index, 0);
+      assert.equal(second.index, 1);
+      assert.equal(second.destination, "Hamburg");
+      assert.ok(second.storageLot.equals(storageLot));
+      const lot = await program.account.storageLot.fetch(storageLot);
+      assert.equal(lot.totalWeightKg.toNumber(), 100);
+      assert.equal(lot.withdrawalCount, 2);
+    });
+
+    it("applies the configured destination limit", async () => {
+      const storageLot = await filledLot();
+      const config = await program.account.programConfig.fetch(configPda());
+      const limit = config.stringLimits.maxDestinationLen || 64;
+      await expectError(withdraw(storageLot, 100, "x".repeat(limit + 1)), "DestinationTooLong");
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Who may verify (synth-361)
+  // ---------------------------------------------------------------------------
+
+  describe("verifier eligibility", () => {
+    it("rejects a farmer verifying their own plot", async () => {
+      const farmer = await fundedKeypair();
+      const { farmPlot } = await registerPlot(farmer);
+      await expectError(recordVerification(farmer, farmPlot, { unregistered: true }), "FarmerCannotVerify");
+    });
+
+    it("requires a registered oracle for a commodity without a policy", async () => {
+      const farmer = await fundedKeypair();
+      const stranger = await fundedKeypair();
+      const { farmPlot } = await registerPlot(farmer);
+      await expectError(recordVerification(stranger, farmPlot, { unregistered: true }), "InsufficientVerifierTier");
+      await recordVerification(await approvedOracle(), farmPlot, { confidence: 100 });
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Expiry crank (synth-324)
+  // ---------------------------------------------------------------------------
+
+  describe("expiry crank", () => {
+    before(async () => {
+      await setPolicy("timber", { verificationValiditySeconds: 1, verifierTierRequired: 1 });
+    });
 
-  it("Is initialized!", async () => {
-    // Add your test here.
-    const program = anchor.workspace.farmtrace;
-    const tx = await program.methods.initialize().rpc();
-    console.log("Your transaction signature", tx);
+    it("always logs the expiry to the plot's audit log", async () => {
+      const farmer = await fundedKeypair();
+      const { farmPlot } = await registerPlot(farmer, { commodity: "timber" });
+      await recordVerification(await approvedOracle(), farmPlot, { confidence: 100 });
+      await new Promise((resolve) => setTimeout(resolve, 3000));
+      const cranker = await fundedKeypair();
+      await program.methods
+        .markExpired()
+        .accountsPartial({
+          farmPlot,
+          auditLog: auditPda(farmPlot),
+          farmerProfile: profilePda(farmer.publicKey),
+          payer: cranker.publicKey,
+        })
+        .signers([cranker])
+        .rpc();
+      const log = await program.account.auditLog.fetch(auditPda(farmPlot));
+      assert.deepEqual(log.entries[log.entries.length - 1].action, { expired: {} });
+    });
   });
 });